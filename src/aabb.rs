@@ -21,6 +21,13 @@ impl AABB {
     }
 
     pub fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> bool {
+        self.hit_range(ray, t_min, t_max).is_some()
+    }
+
+    /// Returns the part of `t_min..t_max` during which the ray is inside the box.
+    pub fn hit_range(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<(f32, f32)> {
+        let mut t_min = t_min;
+        let mut t_max = t_max;
         for i in 0..3 {
             let inv_d = 1. / ray.direction()[i];
             let mut t0 = (self.min[i] - ray.origin()[i]) * inv_d;
@@ -28,13 +35,13 @@ impl AABB {
             if inv_d < 0. {
                 std::mem::swap(&mut t0, &mut t1);
             }
-            let t_min = t0.max(t_min);
-            let t_max = t1.min(t_max);
+            t_min = t0.max(t_min);
+            t_max = t1.min(t_max);
             if t_max <= t_min {
-                return false;
+                return None;
             }
         }
-        true
+        Some((t_min, t_max))
     }
 
     pub fn surrounding_box(box0: &Self, box1: &Self) -> Self {
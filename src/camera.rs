@@ -13,6 +13,10 @@ pub struct Camera {
     u: Vec3,
     v: Vec3,
     time: Time,
+    /// Height of the viewport at unit distance from the camera.
+    viewport_height: f32,
+    /// Angle covered by a single pixel, which rays start their cones with.
+    pixel_spread: f32,
 }
 
 impl Camera {
@@ -51,6 +55,17 @@ impl Camera {
             u,
             v,
             time,
+            viewport_height,
+            pixel_spread: 0.0,
+        }
+    }
+
+    /// Lets rays know how much of the view a pixel covers, so that textures can be
+    /// filtered to the size of a pixel.
+    pub fn with_resolution(self, image_height: usize) -> Self {
+        Self {
+            pixel_spread: self.viewport_height / image_height.max(1) as f32,
+            ..self
         }
     }
 
@@ -76,5 +91,6 @@ impl Camera {
             self.lower_left_corner_direction + s * self.horizontal + t * self.vertical - offset,
             rand::random_range(self.time.start, self.time.end),
         )
+        .with_cone(0.0, self.pixel_spread)
     }
 }
@@ -24,6 +24,16 @@ impl Color {
         self.0[2]
     }
 
+    /// Relative luminance of the linear color, using the Rec. 709 primaries.
+    pub fn luminance(&self) -> f32 {
+        0.2126 * self.r() + 0.7152 * self.g() + 0.0722 * self.b()
+    }
+
+    /// Applies `f` to every channel.
+    pub fn map(&self, f: impl Fn(f32) -> f32) -> Self {
+        Self::new(f(self.r()), f(self.g()), f(self.b()))
+    }
+
     pub fn random() -> Self {
         Self(Vec3::random())
     }
@@ -0,0 +1,115 @@
+/// Piecewise-constant probability distribution over `[0, 1)`, proportional to a function
+/// given by its values on equally sized intervals.
+#[derive(Debug, Clone)]
+pub struct Distribution1D {
+    function: Vec<f32>,
+    cdf: Vec<f32>,
+    integral: f32,
+}
+
+impl Distribution1D {
+    pub fn new(function: Vec<f32>) -> Self {
+        let count = function.len();
+        let mut cdf = Vec::with_capacity(count + 1);
+        cdf.push(0.0);
+        for (i, value) in function.iter().enumerate() {
+            cdf.push(cdf[i] + value.abs() / count as f32);
+        }
+
+        let integral = cdf[count];
+        if integral > 0.0 {
+            for value in &mut cdf {
+                *value /= integral;
+            }
+        } else {
+            // Fall back to a uniform distribution
+            for (i, value) in cdf.iter_mut().enumerate() {
+                *value = i as f32 / count as f32;
+            }
+        }
+
+        Self {
+            function,
+            cdf,
+            integral,
+        }
+    }
+
+    pub fn integral(&self) -> f32 {
+        self.integral
+    }
+
+    /// Maps a uniform random number to a sample in `[0, 1)`. Returns the sample, its
+    /// probability density and the index of the interval it lies in.
+    pub fn sample(&self, u: f32) -> (f32, f32, usize) {
+        // Last interval whose cdf doesn't exceed u
+        let index = self
+            .cdf
+            .partition_point(|&c| c <= u)
+            .clamp(1, self.function.len())
+            - 1;
+
+        let width = self.cdf[index + 1] - self.cdf[index];
+        let offset = if width > 0.0 {
+            (u - self.cdf[index]) / width
+        } else {
+            0.0
+        };
+
+        let x = ((index as f32 + offset) / self.function.len() as f32).min(1.0 - f32::EPSILON);
+        (x, self.pdf(index), index)
+    }
+
+    /// Probability density of samples in the interval `index`.
+    pub fn pdf(&self, index: usize) -> f32 {
+        if self.integral > 0.0 {
+            self.function[index].abs() / self.integral
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Piecewise-constant probability distribution over `[0, 1)²`, proportional to a function
+/// given by its values on a grid of `width` by `height` cells stored row by row.
+#[derive(Debug, Clone)]
+pub struct Distribution2D {
+    /// Distribution of `x` within each row.
+    conditionals: Vec<Distribution1D>,
+    /// Distribution of the rows.
+    marginal: Distribution1D,
+}
+
+impl Distribution2D {
+    pub fn new(function: &[f32], width: usize, height: usize) -> Self {
+        let conditionals: Vec<_> = function
+            .chunks(width)
+            .take(height)
+            .map(|row| Distribution1D::new(row.to_vec()))
+            .collect();
+        let marginal = Distribution1D::new(conditionals.iter().map(|c| c.integral()).collect());
+
+        Self {
+            conditionals,
+            marginal,
+        }
+    }
+
+    /// Maps two uniform random numbers to a sample and its probability density.
+    pub fn sample(&self, u: (f32, f32)) -> ((f32, f32), f32) {
+        let (y, pdf_y, row) = self.marginal.sample(u.1);
+        let (x, pdf_x, _) = self.conditionals[row].sample(u.0);
+        ((x, y), pdf_x * pdf_y)
+    }
+
+    pub fn pdf(&self, x: f32, y: f32) -> f32 {
+        let height = self.conditionals.len();
+        let width = self.conditionals[0].function.len();
+        let row = ((y * height as f32) as usize).min(height - 1);
+        let column = ((x * width as f32) as usize).min(width - 1);
+        if self.marginal.integral() <= 0.0 {
+            return 1.0;
+        }
+        self.conditionals[row].function[column].abs() / self.marginal.integral()
+    }
+}
@@ -0,0 +1,161 @@
+use std::f32::consts::PI;
+
+use thiserror::Error;
+
+use crate::{color::Color, distribution::Distribution2D, textures, vec3::Vec3};
+
+#[derive(Error, Debug)]
+pub enum EnvironmentError {
+    #[error("Failed to load the environment map: {0}")]
+    Image(#[from] image::ImageError),
+}
+
+/// Light arriving from infinitely far away in every direction, stored as an equirectangular
+/// image whose `u` runs around the vertical axis and whose `v` runs from straight up at
+/// the top to straight down at the bottom.
+///
+/// Directions are sampled proportionally to the brightness of the image, so small bright
+/// features such as the sun are found by explicit light sampling instead of by chance.
+#[derive(Debug, Clone)]
+pub struct EnvironmentMap {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+    /// Rotation around the vertical axis, in radians.
+    rotation: f32,
+    intensity: f32,
+    distribution: Distribution2D,
+}
+
+/// Direction sampled towards an environment map.
+pub struct EnvironmentSample {
+    pub direction: Vec3,
+    pub radiance: Color,
+    /// Probability density per solid angle.
+    pub pdf: f32,
+}
+
+impl EnvironmentMap {
+    /// Loads a high dynamic range image such as an `.hdr` or `.exr` file.
+    pub fn load(path: &str) -> Result<Self, EnvironmentError> {
+        let image = textures::open_image(path)?.into_rgb32f();
+        let pixels = image
+            .pixels()
+            .map(|pixel| Color::new(pixel[0], pixel[1], pixel[2]))
+            .collect();
+        Ok(Self::from_pixels(
+            image.width() as usize,
+            image.height() as usize,
+            pixels,
+        ))
+    }
+
+    /// Creates an environment map of `width` by `height` pixels by evaluating `radiance`
+    /// at the direction through the center of each pixel.
+    pub fn from_fn(width: usize, height: usize, radiance: impl Fn(Vec3) -> Color) -> Self {
+        let pixels = (0..height)
+            .flat_map(|j| (0..width).map(move |i| (i, j)))
+            .map(|(i, j)| {
+                let u = (i as f32 + 0.5) / width as f32;
+                let v = (j as f32 + 0.5) / height as f32;
+                radiance(direction_from_uv(u, v))
+            })
+            .collect();
+
+        Self::from_pixels(width, height, pixels)
+    }
+
+    fn from_pixels(width: usize, height: usize, pixels: Vec<Color>) -> Self {
+        // Rows near the poles cover a smaller solid angle, so they are sampled less often
+        let weights: Vec<f32> = pixels
+            .iter()
+            .enumerate()
+            .map(|(index, color)| {
+                let theta = PI * ((index / width) as f32 + 0.5) / height as f32;
+                color.luminance().max(0.0) * theta.sin()
+            })
+            .collect();
+
+        Self {
+            width,
+            height,
+            distribution: Distribution2D::new(&weights, width, height),
+            pixels,
+            rotation: 0.0,
+            intensity: 1.0,
+        }
+    }
+
+    /// Rotates the environment around the vertical axis by `angle` radians.
+    pub fn with_rotation(self, angle: f32) -> Self {
+        Self {
+            rotation: angle,
+            ..self
+        }
+    }
+
+    /// Scales the brightness of the environment.
+    pub fn with_intensity(self, intensity: f32) -> Self {
+        Self { intensity, ..self }
+    }
+
+    /// Radiance arriving from `direction`.
+    pub fn radiance(&self, direction: Vec3) -> Color {
+        let (u, v) = self.uv(direction);
+        self.pixel(u, v)
+    }
+
+    pub fn sample(&self) -> Option<EnvironmentSample> {
+        let ((u, v), pdf_uv) = self.distribution.sample(rand::random());
+        if pdf_uv <= 0.0 {
+            return None;
+        }
+
+        let direction = direction_from_uv((u + self.rotation / (2.0 * PI)).rem_euclid(1.0), v);
+        let sin_theta = (PI * v).sin();
+        if sin_theta <= 0.0 {
+            return None;
+        }
+
+        Some(EnvironmentSample {
+            direction,
+            radiance: self.pixel(u, v),
+            pdf: pdf_uv / (2.0 * PI * PI * sin_theta),
+        })
+    }
+
+    /// Probability density per solid angle of `sample` picking `direction`.
+    pub fn pdf(&self, direction: Vec3) -> f32 {
+        let (u, v) = self.uv(direction);
+        let sin_theta = (PI * v).sin();
+        if sin_theta <= 0.0 {
+            return 0.0;
+        }
+        self.distribution.pdf(u, v) / (2.0 * PI * PI * sin_theta)
+    }
+
+    /// Image coordinates of `direction`, taking the rotation into account.
+    fn uv(&self, direction: Vec3) -> (f32, f32) {
+        let direction = direction.normalized();
+        let theta = direction.y().clamp(-1.0, 1.0).acos();
+        let phi = direction.z().atan2(direction.x()) - self.rotation;
+        ((phi / (2.0 * PI)).rem_euclid(1.0), theta / PI)
+    }
+
+    fn pixel(&self, u: f32, v: f32) -> Color {
+        let i = ((u * self.width as f32) as usize).min(self.width - 1);
+        let j = ((v * self.height as f32) as usize).min(self.height - 1);
+        self.pixels[j * self.width + i] * self.intensity
+    }
+}
+
+/// Direction through the point `(u, v)` of an unrotated environment map.
+fn direction_from_uv(u: f32, v: f32) -> Vec3 {
+    let phi = 2.0 * PI * u;
+    let theta = PI * v;
+    Vec3::new(
+        theta.sin() * phi.cos(),
+        theta.cos(),
+        theta.sin() * phi.sin(),
+    )
+}
@@ -0,0 +1,333 @@
+use std::{f32::consts::PI, fs, path::Path};
+
+use thiserror::Error;
+
+use crate::{materials::Onb, vec3::Vec3};
+
+#[derive(Error, Debug)]
+pub enum IesError {
+    #[error("Failed to read the IES file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The IES file has no TILT line")]
+    MissingTilt,
+    #[error("The IES file ends before all of its data")]
+    UnexpectedEnd,
+    #[error("The IES file has no angles")]
+    NoAngles,
+    #[error("Invalid number in the IES file: {0}")]
+    InvalidNumber(String),
+    #[error("Unsupported photometric type {0}, only type C is supported")]
+    UnsupportedPhotometricType(u32),
+}
+
+/// Luminous intensity distribution of a luminaire, as measured by the manufacturer and
+/// stored in an IESNA LM-63 file.
+///
+/// Intensities are given in candela on a grid of vertical angles, measured from the nadir,
+/// the direction straight out of the luminaire, and horizontal angles around it. Files only
+/// list the angles that aren't mirror images of others.
+#[derive(Debug, Clone)]
+pub struct IesProfile {
+    /// Vertical angles in degrees, ascending.
+    vertical_angles: Vec<f32>,
+    /// Horizontal angles in degrees, ascending.
+    horizontal_angles: Vec<f32>,
+    /// Candela values for each horizontal angle, over all vertical angles.
+    candela: Vec<Vec<f32>>,
+    max_candela: f32,
+    /// Integral of the relative intensity over the half of the sphere around the nadir,
+    /// which is 2π for a uniform profile.
+    intensity_integral: f32,
+}
+
+impl IesProfile {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, IesError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parses the contents of an LM-63 file. Tilt data, which describes how the output
+    /// changes when the lamp is tilted, is skipped.
+    pub fn parse(text: &str) -> Result<Self, IesError> {
+        // Keywords end with the TILT line, after which the file only contains numbers
+        let mut lines = text.lines();
+        let tilt = lines
+            .by_ref()
+            .find_map(|line| line.trim().strip_prefix("TILT="))
+            .ok_or(IesError::MissingTilt)?;
+
+        let rest: Vec<&str> = lines.collect();
+        let mut numbers = rest
+            .iter()
+            .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ','))
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                token
+                    .parse::<f32>()
+                    .map_err(|_| IesError::InvalidNumber(token.to_string()))
+            });
+        let mut next = move || numbers.next().unwrap_or(Err(IesError::UnexpectedEnd));
+
+        if tilt.trim() == "INCLUDE" {
+            let _lamp_to_luminaire_geometry = next()?;
+            let count = next()? as usize;
+            for _ in 0..2 * count {
+                next()?;
+            }
+        }
+
+        let _lamps = next()?;
+        let _lumens_per_lamp = next()?;
+        let multiplier = next()?;
+        let vertical_count = next()? as usize;
+        let horizontal_count = next()? as usize;
+        let photometric_type = next()? as u32;
+        if photometric_type != 1 {
+            return Err(IesError::UnsupportedPhotometricType(photometric_type));
+        }
+
+        let _units = next()?;
+        let _width = next()?;
+        let _length = next()?;
+        let _height = next()?;
+        let ballast_factor = next()?;
+        let _file_generation_type = next()?;
+        let _input_watts = next()?;
+
+        if vertical_count == 0 || horizontal_count == 0 {
+            return Err(IesError::NoAngles);
+        }
+
+        let vertical_angles = (0..vertical_count)
+            .map(|_| next())
+            .collect::<Result<Vec<_>, _>>()?;
+        let horizontal_angles = (0..horizontal_count)
+            .map(|_| next())
+            .collect::<Result<Vec<_>, _>>()?;
+        let candela = (0..horizontal_count)
+            .map(|_| {
+                (0..vertical_count)
+                    .map(|_| next().map(|value| value * multiplier * ballast_factor))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let max_candela = candela
+            .iter()
+            .flatten()
+            .fold(0.0, |max: f32, &c| max.max(c));
+        let mut profile = Self {
+            vertical_angles,
+            horizontal_angles,
+            candela,
+            max_candela,
+            intensity_integral: 0.0,
+        };
+        profile.intensity_integral = profile.integrate_intensity();
+        Ok(profile)
+    }
+
+    /// Luminous intensity in candela at the `vertical` angle from the nadir and the
+    /// `horizontal` angle around it, both in radians.
+    pub fn candela(&self, vertical: f32, horizontal: f32) -> f32 {
+        let vertical = vertical.to_degrees();
+        let (first, last) = (
+            self.vertical_angles[0],
+            self.vertical_angles.last().unwrap(),
+        );
+        if vertical < first || vertical > *last {
+            return 0.0;
+        }
+
+        let (h0, h1, t) = interpolation(&self.horizontal_angles, self.fold_horizontal(horizontal));
+        let column = |h: usize| {
+            let (v0, v1, s) = interpolation(&self.vertical_angles, vertical);
+            lerp(self.candela[h][v0], self.candela[h][v1], s)
+        };
+        lerp(column(h0), column(h1), t)
+    }
+
+    /// Intensity towards `direction` relative to the brightest direction of the profile,
+    /// where `basis` has its `w` along the nadir and its `u` at a horizontal angle of zero.
+    pub fn relative_intensity(&self, direction: Vec3, basis: &Onb) -> f32 {
+        if self.max_candela <= 0.0 {
+            return 0.0;
+        }
+
+        let local = basis.project(direction.normalized());
+        let vertical = local.z().clamp(-1.0, 1.0).acos();
+        let horizontal = local.y().atan2(local.x());
+        self.candela(vertical, horizontal) / self.max_candela
+    }
+
+    pub fn max_candela(&self) -> f32 {
+        self.max_candela
+    }
+
+    pub fn intensity_integral(&self) -> f32 {
+        self.intensity_integral
+    }
+
+    /// Maps a horizontal angle in radians to the range covered by the file, in degrees,
+    /// using the symmetry implied by the last angle.
+    fn fold_horizontal(&self, horizontal: f32) -> f32 {
+        let angle = horizontal.to_degrees().rem_euclid(360.0);
+        let last = *self.horizontal_angles.last().unwrap();
+        if last <= 0.0 {
+            // Rotationally symmetric
+            0.0
+        } else if last <= 90.0 {
+            // Symmetric in each quadrant
+            let angle = if angle > 180.0 { 360.0 - angle } else { angle };
+            if angle > 90.0 {
+                180.0 - angle
+            } else {
+                angle
+            }
+        } else if last <= 180.0 {
+            // Symmetric about the vertical plane through 0 and 180 degrees
+            if angle > 180.0 {
+                360.0 - angle
+            } else {
+                angle
+            }
+        } else {
+            angle
+        }
+    }
+
+    /// Integrates the relative intensity over the half of the sphere around the nadir with
+    /// the midpoint rule.
+    fn integrate_intensity(&self) -> f32 {
+        const STEPS: usize = 90;
+        if self.max_candela <= 0.0 {
+            return 0.0;
+        }
+
+        let d_theta = 0.5 * PI / STEPS as f32;
+        let d_phi = 2.0 * PI / (4 * STEPS) as f32;
+        let mut sum = 0.0;
+        for i in 0..STEPS {
+            let theta = (i as f32 + 0.5) * d_theta;
+            let ring: f32 = (0..4 * STEPS)
+                .map(|j| self.candela(theta, (j as f32 + 0.5) * d_phi))
+                .sum();
+            sum += ring * theta.sin();
+        }
+        sum * d_theta * d_phi / self.max_candela
+    }
+}
+
+/// Indices of the angles around `angle` and the position between them. Angles outside of
+/// the range snap to the nearest end.
+fn interpolation(angles: &[f32], angle: f32) -> (usize, usize, f32) {
+    let upper = angles.partition_point(|&a| a <= angle);
+    if upper == 0 {
+        return (0, 0, 0.0);
+    }
+    if upper == angles.len() {
+        return (upper - 1, upper - 1, 0.0);
+    }
+
+    let lower = upper - 1;
+    let t = (angle - angles[lower]) / (angles[upper] - angles[lower]);
+    (lower, upper, t)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + t * (b - a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUADRANT: &str = "IESNA:LM-63-2002
+[TEST] quadrant symmetric
+TILT=NONE
+1 -1 2.0 3 2 1 2 0 0 0
+0.75 1 100
+0 45 90
+0 90
+100 80 0
+60 40 0
+";
+
+    const BILATERAL: &str = "IESNA:LM-63-2002
+[TEST] bilaterally symmetric
+TILT=NONE
+1 -1 2.0 3 3 1 2 0 0 0
+0.75 1 100
+0 45 90
+0 90 180
+100 80 0
+60 40 0
+20 10 0
+";
+
+    const ISOTROPIC: &str = "IESNA:LM-63-2002
+TILT=NONE
+1 -1 1.0 3 1 1 2 0 0 0
+1.0 1 100
+0 45 90
+0
+10 10 10
+";
+
+    fn assert_near(value: f32, expected: f32) {
+        assert!(
+            (value - expected).abs() < 1e-3 * expected.abs().max(1.0),
+            "{value} != {expected}"
+        );
+    }
+
+    fn candela(profile: &IesProfile, vertical: f32, horizontal: f32) -> f32 {
+        profile.candela(vertical.to_radians(), horizontal.to_radians())
+    }
+
+    #[test]
+    fn scales_tabulated_values_by_multiplier_and_ballast_factor() {
+        let profile = IesProfile::parse(QUADRANT).unwrap();
+        assert_near(candela(&profile, 0.0, 0.0), 150.0);
+        assert_near(candela(&profile, 45.0, 0.0), 120.0);
+        assert_near(candela(&profile, 45.0, 90.0), 60.0);
+        assert_near(profile.max_candela(), 150.0);
+    }
+
+    #[test]
+    fn interpolates_between_angles() {
+        let profile = IesProfile::parse(QUADRANT).unwrap();
+        assert_near(candela(&profile, 22.5, 0.0), 135.0);
+        assert_near(candela(&profile, 45.0, 45.0), 90.0);
+        assert_near(candela(&profile, 22.5, 45.0), 105.0);
+        assert_near(candela(&profile, 120.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn folds_horizontal_angles_into_each_quadrant() {
+        let profile = IesProfile::parse(QUADRANT).unwrap();
+        assert_near(candela(&profile, 45.0, 180.0), 120.0);
+        assert_near(candela(&profile, 45.0, 270.0), 60.0);
+        assert_near(candela(&profile, 45.0, 135.0), 90.0);
+        assert_near(candela(&profile, 45.0, -90.0), 60.0);
+    }
+
+    #[test]
+    fn folds_horizontal_angles_across_the_plane_of_symmetry() {
+        let profile = IesProfile::parse(BILATERAL).unwrap();
+        assert_near(candela(&profile, 45.0, 180.0), 15.0);
+        assert_near(candela(&profile, 45.0, 270.0), 60.0);
+        assert_near(candela(&profile, 45.0, 200.0), 25.0);
+    }
+
+    #[test]
+    fn integrates_a_uniform_profile_over_the_hemisphere() {
+        let profile = IesProfile::parse(ISOTROPIC).unwrap();
+        assert_near(profile.intensity_integral(), 2.0 * PI);
+    }
+
+    #[test]
+    fn requires_a_tilt_line() {
+        let result = IesProfile::parse("IESNA:LM-63-2002\n1 -1 1.0 1 1 1 2 0 0 0\n");
+        assert!(matches!(result, Err(IesError::MissingTilt)));
+    }
+}
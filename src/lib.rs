@@ -2,21 +2,29 @@ use indicatif::ProgressBar;
 
 use crate::camera::Camera;
 use crate::image::Image;
-use crate::scenes::Scene;
+use crate::scenes::{Scene, SceneError};
 pub use crate::settings::Settings;
 
 mod aabb;
 mod camera;
 mod color;
+mod distribution;
+mod environment;
+mod ies;
 mod image;
+mod lights;
 mod materials;
+mod medium;
 mod objects;
 mod perlin;
 mod rand_ext;
 mod ray;
 mod renderer;
 pub mod scenes;
+mod sdf;
 pub mod settings;
+mod sky;
+mod spectrum;
 mod textures;
 mod time;
 pub mod vec3;
@@ -31,29 +39,30 @@ impl ProgressTicker for ProgressBar {
     }
 }
 
-fn setup_scene(settings: Settings) -> (Scene<'static>, Camera, Image) {
+fn setup_scene(settings: Settings) -> Result<(Scene<'static>, Camera, Image), SceneError> {
     // Camera
-    let camera = Camera::from_settings(settings.camera);
+    let camera =
+        Camera::from_settings(settings.camera).with_resolution(settings.image.image_height);
     // World
-    let world = Scene::from_settings(settings.scene);
+    let world = Scene::from_settings(settings.scene)?;
 
     // Image
     let image = Image::from_settings(settings.image);
 
-    (world, camera, image)
+    Ok((world, camera, image))
 }
 
 pub fn render_image_from_settings(
     settings: Settings,
     progress_ticker: &(impl ProgressTicker + Sync),
-) -> Image {
-    let (world, camera, mut image) = setup_scene(settings);
-
+) -> Result<Image, SceneError> {
     // Renderer
     let renderer = renderer::Renderer::from_settings(settings.renderer);
 
+    let (world, camera, mut image) = setup_scene(settings)?;
+
     // Render
     renderer.render_image(&mut image, &world, &camera, progress_ticker);
 
-    image
+    Ok(image)
 }
@@ -0,0 +1,471 @@
+use std::{f32::consts::PI, fmt, sync::Arc};
+
+use thiserror::Error;
+
+use crate::{
+    color::Color,
+    distribution::Distribution2D,
+    ies::IesProfile,
+    materials::{DiffuseLight, LightPower, Material, Onb, LUMENS_PER_WATT},
+    objects::{HitRecord, Rectangle, RectangleError},
+    ray::Ray,
+    vec3::{Point3, Vec3},
+};
+
+#[derive(Error, Debug)]
+pub enum AreaLightError {
+    #[error("The edges of the area light are parallel or zero")]
+    Degenerate,
+    #[error("The area light is black, so it can't emit any power")]
+    Black,
+    #[error(transparent)]
+    Rectangle(#[from] RectangleError),
+}
+
+/// Resolution of the grid over which area lights tabulate the brightness of their emission.
+const AREA_LIGHT_RESOLUTION: usize = 128;
+
+/// Light that is sampled explicitly. Apart from area lights and the disc of directional
+/// lights, rays never hit it, so it only contributes to the image through light sampling,
+/// which makes sharp shadows and spotlights possible.
+#[derive(Debug, Clone)]
+pub enum Light {
+    /// Emits `intensity` in watts per steradian evenly in all directions, or shaped by a
+    /// photometric profile whose nadir points straight down.
+    Point {
+        position: Point3,
+        intensity: Color,
+        profile: Option<Arc<IesProfile>>,
+    },
+    /// Emits `intensity` in a cone around `direction`, fading out between the cosines of
+    /// the inner and outer half-angles. A photometric profile has its nadir along
+    /// `direction`.
+    Spot {
+        position: Point3,
+        direction: Vec3,
+        intensity: Color,
+        cos_inner: f32,
+        cos_outer: f32,
+        profile: Option<Arc<IesProfile>>,
+    },
+    /// Infinitely far away light such as the sun, which covers a disc of the sky around
+    /// `direction` and delivers `irradiance` to surfaces facing it.
+    Directional {
+        direction: Vec3,
+        irradiance: Color,
+        cos_radius: f32,
+    },
+    /// Emitting surface that is also part of the scene.
+    Area(Arc<AreaLight>),
+}
+
+/// Light arriving at a point from a sampled direction.
+pub struct LightSample {
+    /// Unit vector pointing from the point towards the light.
+    pub direction: Vec3,
+    /// Distance to the light, infinite for directional lights.
+    pub distance: f32,
+    /// Incoming radiance divided by the probability density of the direction.
+    pub radiance: Color,
+    /// Probability density per solid angle of the direction, zero for lights at a point.
+    pub pdf: f32,
+}
+
+impl Light {
+    pub fn point(position: Point3, intensity: Color) -> Self {
+        Self::Point {
+            position,
+            intensity,
+            profile: None,
+        }
+    }
+
+    /// Creates a spotlight at `position` pointing at `target`. It is fully lit up to
+    /// `inner_angle` from its axis and fades out until `outer_angle`, both in radians.
+    pub fn spot(
+        position: Point3,
+        target: Point3,
+        intensity: Color,
+        inner_angle: f32,
+        outer_angle: f32,
+    ) -> Self {
+        Self::Spot {
+            position,
+            direction: (target - position).normalized(),
+            intensity,
+            cos_inner: inner_angle.cos(),
+            cos_outer: outer_angle.cos(),
+            profile: None,
+        }
+    }
+
+    /// Creates a sun in the direction `towards_sun`, whose disc spans `angular_diameter`
+    /// radians. The real sun spans about half a degree, larger discs give softer shadows.
+    pub fn sun(towards_sun: Vec3, irradiance: Color, angular_diameter: f32) -> Self {
+        Self::Directional {
+            direction: towards_sun.normalized(),
+            irradiance,
+            cos_radius: (0.5 * angular_diameter).cos(),
+        }
+    }
+
+    /// Shapes the light of a point or spot light by a photometric profile, scaled so its
+    /// brightest direction gets the full intensity. Directional lights are left unchanged.
+    pub fn with_profile(self, profile: IesProfile) -> Self {
+        let profile = Some(Arc::new(profile));
+        match self {
+            Light::Point {
+                position,
+                intensity,
+                ..
+            } => Light::Point {
+                position,
+                intensity,
+                profile,
+            },
+            Light::Spot {
+                position,
+                direction,
+                intensity,
+                cos_inner,
+                cos_outer,
+                ..
+            } => Light::Spot {
+                position,
+                direction,
+                intensity,
+                cos_inner,
+                cos_outer,
+                profile,
+            },
+            Light::Directional { .. } | Light::Area(_) => self,
+        }
+    }
+
+    /// Shapes the light of a point or spot light by a photometric profile and takes the
+    /// intensity from the candela in the file, at 683 lumens per watt. The intensity the
+    /// light was created with only sets its color. Directional lights are left unchanged.
+    pub fn with_candela(self, profile: IesProfile) -> Self {
+        let watts_per_steradian = profile.max_candela() / LUMENS_PER_WATT;
+        let mut light = self.with_profile(profile);
+        if let Light::Point { intensity, .. } | Light::Spot { intensity, .. } = &mut light {
+            let luminance = intensity.luminance();
+            if luminance > 0.0 {
+                *intensity = *intensity * (watts_per_steradian / luminance);
+            }
+        }
+        light
+    }
+
+    /// Samples a direction from `point` towards the light.
+    pub fn sample(&self, point: Point3) -> Option<LightSample> {
+        match *self {
+            Light::Point {
+                position,
+                intensity,
+                ref profile,
+            } => {
+                let (direction, distance) = towards(point, position)?;
+                let nadir = Vec3::new(0.0, -1.0, 0.0);
+                let falloff = profile_falloff(profile, -direction, nadir);
+                if falloff <= 0.0 {
+                    return None;
+                }
+                Some(LightSample {
+                    direction,
+                    distance,
+                    radiance: intensity * (falloff / (distance * distance)),
+                    pdf: 0.0,
+                })
+            }
+            Light::Spot {
+                position,
+                direction: axis,
+                intensity,
+                cos_inner,
+                cos_outer,
+                ref profile,
+            } => {
+                let (direction, distance) = towards(point, position)?;
+                let falloff = smoothstep(cos_outer, cos_inner, (-direction).dot(axis))
+                    * profile_falloff(profile, -direction, axis);
+                if falloff <= 0.0 {
+                    return None;
+                }
+                Some(LightSample {
+                    direction,
+                    distance,
+                    radiance: intensity * (falloff / (distance * distance)),
+                    pdf: 0.0,
+                })
+            }
+            Light::Directional {
+                direction,
+                irradiance,
+                cos_radius,
+            } => {
+                // Uniformly sample the cone covered by the disc. The radiance of the disc
+                // times its solid angle is the irradiance.
+                let (r1, r2): (f32, f32) = rand::random();
+                let cos_theta = 1.0 - r1 * (1.0 - cos_radius);
+                let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+                let phi = 2.0 * PI * r2;
+                let local = Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+                Some(LightSample {
+                    direction: Onb::from_w(direction).local(local),
+                    distance: f32::INFINITY,
+                    radiance: irradiance,
+                    pdf: cone_pdf(cos_radius),
+                })
+            }
+            Light::Area(ref area) => area.sample(point),
+        }
+    }
+
+    /// Probability density per solid angle of `sample` picking `target` from `origin`,
+    /// where `target` is a point on a surface that a ray from `origin` hit.
+    pub fn pdf(&self, origin: Point3, target: Point3) -> f32 {
+        match self {
+            Light::Area(area) => area.pdf(origin, target),
+            Light::Point { .. } | Light::Spot { .. } | Light::Directional { .. } => 0.0,
+        }
+    }
+
+    /// Radiance arriving from `direction` at rays that leave the scene, together with the
+    /// probability density of `sample` picking the direction. Only the disc of a
+    /// directional light can be seen this way.
+    pub fn radiance(&self, direction: Vec3) -> Option<(Color, f32)> {
+        match *self {
+            Light::Directional {
+                direction: axis,
+                irradiance,
+                cos_radius,
+            } => {
+                let pdf = cone_pdf(cos_radius);
+                if pdf <= 0.0 || direction.normalized().dot(axis) < cos_radius {
+                    return None;
+                }
+                Some((irradiance * pdf, pdf))
+            }
+            Light::Point { .. } | Light::Spot { .. } | Light::Area(_) => None,
+        }
+    }
+}
+
+impl From<AreaLight> for Light {
+    fn from(area: AreaLight) -> Self {
+        Light::Area(Arc::new(area))
+    }
+}
+
+/// Emitting parallelogram that is sampled in proportion to the brightness of its emission,
+/// so that textured emitters such as screens and neon signs light the scene with little
+/// noise. Rays only hit it once its [`object`](AreaLight::object) is added to the scene.
+#[derive(Clone)]
+pub struct AreaLight {
+    corner: Point3,
+    edge_u: Vec3,
+    edge_v: Vec3,
+    /// Unit normal on the front face.
+    normal: Vec3,
+    area: f32,
+    material: DiffuseLight<'static>,
+    /// Average color of the emitted texture, which the power of the light is spread over.
+    average: Color,
+    distribution: Distribution2D,
+    object: Rectangle<'static>,
+}
+
+impl fmt::Debug for AreaLight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AreaLight")
+            .field("corner", &self.corner)
+            .field("edge_u", &self.edge_u)
+            .field("edge_v", &self.edge_v)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AreaLight {
+    /// Creates a light spanning `edge_u` and `edge_v` from `corner`, along which the texture
+    /// coordinates run like on a [`Rectangle`]. The front face is the one that the cross
+    /// product of the edges points away from.
+    pub fn new(
+        corner: Point3,
+        edge_u: Vec3,
+        edge_v: Vec3,
+        material: DiffuseLight<'static>,
+    ) -> Result<Self, AreaLightError> {
+        let cross = edge_u.cross(edge_v);
+        if cross.near_zero() || !cross.length().is_finite() {
+            return Err(AreaLightError::Degenerate);
+        }
+
+        let object = surface(corner, edge_u, edge_v, material.clone())?;
+        let resolution = AREA_LIGHT_RESOLUTION;
+        let colors: Vec<Color> = (0..resolution)
+            .flat_map(|j| (0..resolution).map(move |i| (i, j)))
+            .map(|(i, j)| {
+                let u = (i as f32 + 0.5) / resolution as f32;
+                let v = (j as f32 + 0.5) / resolution as f32;
+                material.texture_color(u, v, &(corner + u * edge_u + v * edge_v))
+            })
+            .collect();
+        let weights: Vec<f32> = colors.iter().map(Color::luminance).collect();
+        let average = colors
+            .into_iter()
+            .fold(Color::new(0.0, 0.0, 0.0), |sum, color| sum + color)
+            / (resolution * resolution) as f32;
+
+        Ok(Self {
+            corner,
+            edge_u,
+            edge_v,
+            normal: cross.normalized(),
+            area: cross.length(),
+            material,
+            average,
+            distribution: Distribution2D::new(&weights, resolution, resolution),
+            object,
+        })
+    }
+
+    /// Scales the emission so the light emits `power` in total, spread over its area and
+    /// in proportion to its texture. Resizing the light keeps the brightness of the scene
+    /// the same.
+    pub fn with_power(self, power: LightPower) -> Result<Self, AreaLightError> {
+        let material = self
+            .material
+            .with_power(power, self.average, self.area)
+            .ok_or(AreaLightError::Black)?;
+
+        Ok(Self {
+            object: surface(self.corner, self.edge_u, self.edge_v, material.clone())?,
+            material,
+            ..self
+        })
+    }
+
+    /// The emitting surface, to be added to the scene.
+    pub fn object(&self) -> Rectangle<'static> {
+        self.object.clone()
+    }
+
+    fn sample(&self, point: Point3) -> Option<LightSample> {
+        let ((u, v), _) = self.distribution.sample(rand::random());
+        let target = self.corner + u * self.edge_u + v * self.edge_v;
+        let (direction, distance) = towards(point, target)?;
+        let pdf = self.pdf(point, target);
+        if pdf <= 0.0 {
+            return None;
+        }
+
+        // The shadow ray stops just short of the light so it doesn't hit the light itself
+        Some(LightSample {
+            direction,
+            distance: distance * (1.0 - 1e-4),
+            radiance: self.emitted(point, target, u, v) / pdf,
+            pdf,
+        })
+    }
+
+    fn pdf(&self, origin: Point3, target: Point3) -> f32 {
+        let offset = target - self.corner;
+        if offset.dot(self.normal).abs() > 1e-3 * self.area.sqrt() {
+            return 0.0;
+        }
+        let u = offset.cross(self.edge_v).dot(self.normal) / self.area;
+        let v = self.edge_u.cross(offset).dot(self.normal) / self.area;
+        if !(0.0..=1.0).contains(&u) || !(0.0..=1.0).contains(&v) {
+            return 0.0;
+        }
+
+        let Some((direction, distance)) = towards(origin, target) else {
+            return 0.0;
+        };
+        let cos_theta = direction.dot(self.normal).abs();
+        if cos_theta <= 0.0 {
+            return 0.0;
+        }
+
+        // Converts the density per area to one per solid angle
+        self.distribution.pdf(u, v) / self.area * distance * distance / cos_theta
+    }
+
+    /// Radiance emitted from `target` towards `point`, evaluated by the material like for
+    /// a ray hitting the surface.
+    fn emitted(&self, point: Point3, target: Point3, u: f32, v: f32) -> Color {
+        let ray = Ray::new(point, target - point);
+        let (normal, front_face) = HitRecord::orient_towards_ray(&ray, self.normal);
+        let tangent = self.edge_u.normalized();
+        let hit_record = HitRecord {
+            point: target,
+            normal,
+            t: 1.0,
+            front_face,
+            material: &self.material,
+            u,
+            v,
+            tangent,
+            bitangent: self.normal.cross(tangent),
+            footprint: 0.0,
+        };
+        self.material.emitted(&ray, &hit_record)
+    }
+}
+
+/// Rectangle spanning `edge_u` and `edge_v` from `corner`, with its normal along the cross
+/// product of the edges.
+fn surface(
+    corner: Point3,
+    edge_u: Vec3,
+    edge_v: Vec3,
+    material: DiffuseLight<'static>,
+) -> Result<Rectangle<'static>, RectangleError> {
+    Rectangle::from_points(
+        [
+            corner,
+            corner + edge_u,
+            corner + edge_u + edge_v,
+            corner + edge_v,
+        ],
+        material,
+    )
+}
+
+/// Density of uniformly sampling a cone, which is zero for a cone without width since that
+/// is a single direction.
+fn cone_pdf(cos_radius: f32) -> f32 {
+    let solid_angle = 2.0 * PI * (1.0 - cos_radius);
+    if solid_angle <= 0.0 {
+        return 0.0;
+    }
+    1.0 / solid_angle
+}
+
+/// Relative intensity of the profile in the `outgoing` direction, with horizontal angles
+/// starting along the x axis or as close to it as the nadir allows.
+fn profile_falloff(profile: &Option<Arc<IesProfile>>, outgoing: Vec3, nadir: Vec3) -> f32 {
+    profile.as_ref().map_or(1.0, |profile| {
+        let basis = Onb::from_w_and_tangent(nadir, Vec3::new(1.0, 0.0, 0.0));
+        profile.relative_intensity(outgoing, &basis)
+    })
+}
+
+/// Unit vector and distance from `from` to `to`.
+fn towards(from: Point3, to: Point3) -> Option<(Vec3, f32)> {
+    let offset = to - from;
+    let distance = offset.length();
+    if distance <= 0.0 {
+        return None;
+    }
+    Some((offset / distance, distance))
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    if edge0 == edge1 {
+        return if x < edge0 { 0.0 } else { 1.0 };
+    }
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
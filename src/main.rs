@@ -1,9 +1,9 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Parser;
 use indicatif::{ProgressBar, ProgressStyle};
 use prettytable::table;
 
-use raytracing::scenes::SceneType;
+use raytracing::{scenes::SceneType, settings::EnvironmentSettings};
 
 fn seperated<T>(num: T) -> String
 where
@@ -25,12 +25,44 @@ struct Cli {
     /// The scene to render
     #[arg(short = 't', long, value_enum)]
     scene_type: SceneType,
+
+    /// Trace wavelengths instead of RGB, which renders dispersion
+    #[arg(long)]
+    spectral: bool,
+
+    /// Equirectangular `.hdr` or `.exr` image that lights the scene from all directions,
+    /// in place of its background
+    #[arg(long, value_name = "FILE")]
+    environment: Option<String>,
+
+    /// Rotation of the environment around the vertical axis, in degrees
+    #[arg(long, value_name = "DEGREES", allow_negative_numbers = true)]
+    environment_rotation: Option<f32>,
+
+    /// Factor the brightness of the environment is scaled by
+    #[arg(long, value_name = "FACTOR")]
+    environment_intensity: Option<f32>,
 }
 
 fn main() -> Result<()> {
     let args = Cli::parse();
 
-    let settings = raytracing::Settings::from_scene_type(args.scene_type);
+    let mut settings = raytracing::Settings::from_scene_type(args.scene_type);
+    settings.renderer.spectral |= args.spectral;
+    if let Some(path) = args.environment {
+        settings.scene.environment = Some(EnvironmentSettings::new(path));
+    }
+    if args.environment_rotation.is_some() || args.environment_intensity.is_some() {
+        let Some(environment) = settings.scene.environment.as_mut() else {
+            bail!("The scene has no environment to adjust, set one with --environment");
+        };
+        if let Some(rotation) = args.environment_rotation {
+            environment.rotation = rotation.to_radians();
+        }
+        if let Some(intensity) = args.environment_intensity {
+            environment.intensity = intensity;
+        }
+    }
 
     let nr_pixels = settings.image.image_width * settings.image.image_height;
     // Set up progress bar
@@ -46,7 +78,7 @@ fn main() -> Result<()> {
     let now = std::time::Instant::now();
 
     // Render
-    let image = raytracing::render_image_from_settings(settings, &progress);
+    let image = raytracing::render_image_from_settings(settings.clone(), &progress)?;
 
     let render_time = now.elapsed();
     let single_core_render_time = render_time.mul_f32(num_cpus::get() as f32);
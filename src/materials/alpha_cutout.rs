@@ -0,0 +1,79 @@
+use crate::{
+    color::Color,
+    medium::Medium,
+    objects::HitRecord,
+    rand_ext::rand,
+    ray::Ray,
+    textures::{BoxedTexture, Texture},
+    vec3::Vec3,
+};
+
+use super::{BoxedMaterial, Material, ScatterRecord};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlphaMode {
+    /// The surface exists where the opacity is at least the given value.
+    Threshold(f32),
+    /// Rays pass through with a probability of one minus the opacity, which averages
+    /// out to smooth, semi-transparent edges.
+    Stochastic,
+}
+
+/// Cuts holes into the surface of another material, using the alpha channel of a texture
+/// as opacity. Meant for leaves, fences and similar quads with transparent textures.
+#[derive(Clone)]
+pub struct AlphaCutout<'a> {
+    base: BoxedMaterial<'a>,
+    opacity: BoxedTexture<'a>,
+    mode: AlphaMode,
+}
+
+impl<'a> AlphaCutout<'a> {
+    pub fn new(
+        base: impl Material + Send + Sync + 'a,
+        opacity: impl Texture + Send + Sync + 'a,
+        mode: AlphaMode,
+    ) -> Self {
+        Self {
+            base: Box::new(base),
+            opacity: Box::new(opacity),
+            mode,
+        }
+    }
+}
+
+impl Material for AlphaCutout<'_> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
+        self.base.scatter(ray, hit_record)
+    }
+
+    fn emitted(&self, ray: &Ray, hit_record: &HitRecord) -> Color {
+        self.base.emitted(ray, hit_record)
+    }
+
+    fn eval(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        self.base.eval(ray, hit_record, direction)
+    }
+
+    fn pdf(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        self.base.pdf(ray, hit_record, direction)
+    }
+
+    fn alpha_test(&self, u: f32, v: f32, point: &Vec3) -> bool {
+        let alpha = self.opacity.alpha(u, v, point);
+        let passes = match self.mode {
+            AlphaMode::Threshold(threshold) => alpha >= threshold,
+            AlphaMode::Stochastic => alpha >= 1.0 || rand::random::<f32>() < alpha,
+        };
+
+        passes && self.base.alpha_test(u, v, point)
+    }
+
+    fn medium(&self) -> Option<Medium> {
+        self.base.medium()
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.base.is_dispersive()
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{color::Color, medium::Medium, objects::HitRecord, ray::Ray, vec3::Vec3};
+
+use super::{fresnel, BoxedMaterial, Material, ScatterRecord};
+
+/// How often light may bounce between the coat and the base before it counts as absorbed.
+const MAX_BOUNCES: usize = 64;
+
+/// Thin, smooth dielectric coat on top of any other material, such as the varnish on wood
+/// or the clearcoat of car paint.
+///
+/// Light is traced through the layers stochastically: it either reflects off the coat or
+/// refracts into it, scatters off the base, and then has to get back out through the coat,
+/// possibly reflecting between the coat and the base several times on the way. This
+/// accounts for all the light the base reflects, so a white base under a clear coat
+/// reflects all light.
+///
+/// Only light that passes through the coat, scatters off the base once and leaves again can
+/// be evaluated for a given pair of directions, so that is the part light sampling finds.
+/// Light reflected by the coat itself, or between the coat and the base, is only found by
+/// scattered rays, so point, spot and directional lights don't show up in the coat.
+#[derive(Clone)]
+pub struct Coated<'a> {
+    base: BoxedMaterial<'a>,
+    refraction_index: f32,
+    /// Absorption of the coat, scaled so its thickness is one unit of distance.
+    absorption: Option<Medium>,
+}
+
+impl<'a> Coated<'a> {
+    pub fn new(base: impl Material + Send + Sync + 'a, refraction_index: f32) -> Self {
+        Self {
+            base: Box::new(base),
+            refraction_index,
+            absorption: None,
+        }
+    }
+
+    /// Tints the coat, so light passing straight through it once turns into `color`.
+    /// Light passing at an angle travels further through the coat and gets tinted more.
+    pub fn with_absorption(self, color: Color) -> Self {
+        Self {
+            absorption: Some(Medium::absorbing(color, 1.0)),
+            ..self
+        }
+    }
+
+    /// Attenuation of a single pass through the coat along `direction`.
+    fn transmittance(&self, direction: Vec3, normal: Vec3) -> Color {
+        match self.absorption {
+            Some(medium) => {
+                let cos = direction.normalized().dot(normal).abs().max(1e-4);
+                medium.transmittance(1.0 / cos)
+            }
+            None => Color::new(1.0, 1.0, 1.0),
+        }
+    }
+
+    /// Fraction of light that refracts into the coat, leaves it again without reflecting
+    /// back down, times the change in solid angle between the direction the base scatters
+    /// into and the one it leaves the coat in. Takes the cosines to the normal of the
+    /// incoming ray above the coat, the light going up inside it and the outgoing light.
+    fn passage(&self, cos_outside: f32, cos_inside: f32, cos_outgoing: f32) -> f32 {
+        let eta = self.refraction_index;
+        let entering = 1.0 - fresnel::dielectric(cos_outside, eta);
+        let leaving = 1.0 - fresnel::dielectric(-cos_inside, eta);
+        entering * leaving * cos_outgoing / (eta * eta * cos_inside)
+    }
+
+    /// Light leaving along `direction` after passing through the coat and scattering off
+    /// the base once: the ray that hits the base, the direction it has to scatter into and
+    /// its [`passage`](Self::passage) through the coat.
+    fn through_coat(
+        &self,
+        ray: &Ray,
+        hit_record: &HitRecord,
+        direction: Vec3,
+    ) -> Option<(Ray, Vec3, f32)> {
+        let normal = hit_record.normal;
+        let incoming = ray.direction().normalized();
+        let outgoing = direction.normalized();
+        let cos_outgoing = outgoing.dot(normal);
+        if cos_outgoing <= 0.0 {
+            return None;
+        }
+
+        let down = fresnel::refract(-incoming, normal, self.refraction_index)?;
+        let up = -fresnel::refract(outgoing, normal, self.refraction_index)?;
+        let inside = Ray::new_time_based(hit_record.point - down, down, ray.time());
+        let passage = self.passage(-incoming.dot(normal), up.dot(normal), cos_outgoing);
+        Some((inside, up, passage))
+    }
+}
+
+impl Material for Coated<'_> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
+        let normal = hit_record.normal;
+        let incoming = ray.direction().normalized();
+        let cos_outside = -incoming.dot(normal);
+
+        // Reflect off the top of the coat
+        if rand::random::<f32>() < fresnel::dielectric(cos_outside, self.refraction_index) {
+            return Some(ScatterRecord {
+                attenuation: Color::new(1.0, 1.0, 1.0),
+                scattered: Ray::new_time_based(
+                    hit_record.point,
+                    incoming.reflect(normal),
+                    ray.time(),
+                ),
+                pdf: 0.0,
+            });
+        }
+
+        let mut direction = fresnel::refract(-incoming, normal, self.refraction_index)?;
+        let mut attenuation = Color::new(1.0, 1.0, 1.0);
+        for bounce in 0..MAX_BOUNCES {
+            // Down through the coat to the base
+            attenuation *= self.transmittance(direction, normal);
+            let inside = Ray::new_time_based(hit_record.point - direction, direction, ray.time());
+            let scatter = self.base.scatter(&inside, hit_record)?;
+            attenuation *= scatter.attenuation;
+
+            // The base transmitted the light, so it never reaches the coat again
+            direction = scatter.scattered.direction().normalized();
+            if direction.dot(normal) <= 0.0 {
+                return Some(ScatterRecord {
+                    attenuation,
+                    scattered: scatter.scattered,
+                    pdf: 0.0,
+                });
+            }
+
+            // Back up through the coat, where it either leaves or reflects down again
+            attenuation *= self.transmittance(direction, normal);
+            let cos_inside = direction.dot(normal);
+            if rand::random::<f32>() < fresnel::dielectric(-cos_inside, self.refraction_index) {
+                direction = direction.reflect(normal);
+                continue;
+            }
+
+            let outgoing = fresnel::refract(-direction, -normal, 1.0 / self.refraction_index)?;
+            // Light that went straight through is also found by light sampling, which needs
+            // the density of this path to weigh the two against each other
+            let pdf = if bounce == 0 {
+                scatter.pdf * self.passage(cos_outside, cos_inside, outgoing.dot(normal))
+            } else {
+                0.0
+            };
+            return Some(ScatterRecord {
+                attenuation,
+                scattered: Ray::new_time_based(hit_record.point, outgoing, ray.time()),
+                pdf,
+            });
+        }
+
+        None
+    }
+
+    /// Light emitted by the base, which shines through the coat unchanged.
+    fn emitted(&self, ray: &Ray, hit_record: &HitRecord) -> Color {
+        self.base.emitted(ray, hit_record)
+    }
+
+    /// Light that passed through the coat and scattered off the base once, leaving out the
+    /// reflections off the coat.
+    fn eval(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        let normal = hit_record.normal;
+        self.through_coat(ray, hit_record, direction).map_or(
+            Color::new(0.0, 0.0, 0.0),
+            |(inside, up, passage)| {
+                self.base.eval(&inside, hit_record, up)
+                    * self.transmittance(inside.direction(), normal)
+                    * self.transmittance(up, normal)
+                    * passage
+            },
+        )
+    }
+
+    fn pdf(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        self.through_coat(ray, hit_record, direction)
+            .map_or(0.0, |(inside, up, passage)| {
+                self.base.pdf(&inside, hit_record, up) * passage
+            })
+    }
+
+    fn alpha_test(&self, u: f32, v: f32, point: &Vec3) -> bool {
+        self.base.alpha_test(u, v, point)
+    }
+
+    fn medium(&self) -> Option<Medium> {
+        self.base.medium()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use crate::materials::{
+        testing::{furnace, hit_from_above},
+        Lambertian,
+    };
+
+    use super::*;
+
+    #[test]
+    fn clear_coat_on_white_base_reflects_all_light() {
+        let coated = Coated::new(Lambertian::new(Color::new(1.0, 1.0, 1.0)), 1.5);
+        let reflected = furnace(&coated);
+        for channel in [reflected.r(), reflected.g(), reflected.b()] {
+            assert!((channel - 1.0).abs() < 0.01, "reflected {channel}");
+        }
+    }
+
+    #[test]
+    fn absorbing_coat_reflects_less_light() {
+        let coated = Coated::new(Lambertian::new(Color::new(1.0, 1.0, 1.0)), 1.5)
+            .with_absorption(Color::new(0.9, 0.5, 0.1));
+        let reflected = furnace(&coated);
+        assert!(reflected.r() < 1.0);
+        assert!(reflected.b() < reflected.g() && reflected.g() < reflected.r());
+    }
+
+    #[test]
+    fn evaluation_matches_light_scattered_straight_through_the_coat() {
+        const SAMPLES: usize = 200_000;
+        let coated = Coated::new(Lambertian::new(Color::new(0.8, 0.8, 0.8)), 1.5)
+            .with_absorption(Color::new(0.9, 0.7, 0.5));
+        let hit_record = hit_from_above(&coated);
+        let direction = Vec3::new(0.5, 0.0, -1.0);
+        let ray = Ray::new(Vec3::zero() - direction, direction);
+
+        // Scattered rays that light sampling could have found too
+        let mut scattered = Color::new(0.0, 0.0, 0.0);
+        let mut sampled = 0;
+        for _ in 0..SAMPLES {
+            if let Some(scatter) = coated.scatter(&ray, &hit_record) {
+                if scatter.pdf > 0.0 {
+                    scattered += scatter.attenuation;
+                    sampled += 1;
+                }
+            }
+        }
+
+        // The same light and probability integrated over uniformly sampled directions
+        let mut evaluated = Color::new(0.0, 0.0, 0.0);
+        let mut probability = 0.0;
+        for _ in 0..SAMPLES {
+            let outgoing = Vec3::random_in_hemisphere(hit_record.normal);
+            evaluated += coated.eval(&ray, &hit_record, outgoing) * (2.0 * PI);
+            probability += coated.pdf(&ray, &hit_record, outgoing) * (2.0 * PI);
+        }
+
+        let scattered = scattered / SAMPLES as f32;
+        let evaluated = evaluated / SAMPLES as f32;
+        for (scattered, evaluated) in [
+            (scattered.r(), evaluated.r()),
+            (scattered.g(), evaluated.g()),
+            (scattered.b(), evaluated.b()),
+        ] {
+            assert!(
+                (scattered - evaluated).abs() < 0.01,
+                "scattered {scattered}, evaluated {evaluated}"
+            );
+        }
+        let sampled = sampled as f32 / SAMPLES as f32;
+        let probability = probability / SAMPLES as f32;
+        assert!(
+            (sampled - probability).abs() < 0.01,
+            "sampled {sampled}, probability {probability}"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{color::Color, objects::HitRecord, ray::Ray, vec3::Vec3};
+
+use super::{fresnel, microfacet::TrowbridgeReitz, Material, Onb, ScatterRecord, ThinFilm};
+
+/// Metal with a GGX microfacet surface and a complex index of refraction.
+///
+/// Unlike `Metal`, the reflectance follows from the physical constants of the metal
+/// and only the light blocked by neighbouring microfacets is lost.
+#[derive(Clone)]
+pub struct Conductor<'a> {
+    eta: Color,
+    k: Color,
+    distribution: TrowbridgeReitz,
+    thin_film: Option<ThinFilm<'a>>,
+}
+
+impl<'a> Conductor<'a> {
+    /// Creates a conductor with the index of refraction `eta` and absorption coefficient `k`
+    /// sampled at red, green and blue, and an isotropic roughness in `0..=1`.
+    pub fn new(eta: Color, k: Color, roughness: f32) -> Self {
+        Self::anisotropic(eta, k, roughness, roughness)
+    }
+
+    /// Creates a conductor that is rough by different amounts along the surface tangent,
+    /// which follows increasing `u`, and the bitangent, such as brushed metal.
+    pub fn anisotropic(eta: Color, k: Color, roughness_u: f32, roughness_v: f32) -> Self {
+        Self {
+            eta,
+            k,
+            distribution: TrowbridgeReitz::new(roughness_u, roughness_v),
+            thin_film: None,
+        }
+    }
+
+    pub fn gold(roughness: f32) -> Self {
+        Self::new(
+            Color::new(0.143, 0.374, 1.442),
+            Color::new(3.983, 2.385, 1.603),
+            roughness,
+        )
+    }
+
+    pub fn copper(roughness: f32) -> Self {
+        Self::new(
+            Color::new(0.200, 0.924, 1.102),
+            Color::new(3.912, 2.452, 2.142),
+            roughness,
+        )
+    }
+
+    pub fn aluminium(roughness: f32) -> Self {
+        Self::new(
+            Color::new(1.657, 0.880, 0.521),
+            Color::new(9.224, 6.270, 4.837),
+            roughness,
+        )
+    }
+
+    pub fn silver(roughness: f32) -> Self {
+        Self::new(
+            Color::new(0.155, 0.117, 0.138),
+            Color::new(4.828, 3.122, 2.147),
+            roughness,
+        )
+    }
+
+    /// Keeps the color of the metal but changes how rough it is along the tangent and bitangent.
+    pub fn with_roughness(self, roughness_u: f32, roughness_v: f32) -> Self {
+        Self {
+            distribution: TrowbridgeReitz::new(roughness_u, roughness_v),
+            ..self
+        }
+    }
+
+    /// Covers the metal with a thin film, such as the oxide layer of heat-tinted titanium.
+    pub fn with_thin_film(self, thin_film: ThinFilm<'a>) -> Self {
+        Self {
+            thin_film: Some(thin_film),
+            ..self
+        }
+    }
+
+    fn fresnel(&self, ray: &Ray, hit_record: &HitRecord, cos_theta_i: f32) -> Color {
+        match &self.thin_film {
+            Some(film) => film.reflectance(ray, hit_record, cos_theta_i, 1.0, |lambda| {
+                (
+                    at_wavelength(self.eta, lambda),
+                    at_wavelength(self.k, lambda),
+                )
+            }),
+            None => fresnel::conductor(cos_theta_i, self.eta, self.k),
+        }
+    }
+
+    /// BSDF times the cosine of `wi`, and the density of sampling it, for local directions.
+    /// Both are zero for smooth metal, which only reflects into the mirror direction.
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, wo: Vec3, wi: Vec3) -> (Color, f32) {
+        if self.distribution.is_smooth() || wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return (Color::new(0.0, 0.0, 0.0), 0.0);
+        }
+
+        let wm = (wo + wi).normalized();
+        let f = self.distribution.d(wm) * self.distribution.g(wo, wi) / (4.0 * wo.z());
+        let pdf = self.distribution.visible_normal_pdf(wo, wm) / (4.0 * wo.dot(wm));
+        (self.fresnel(ray, hit_record, wo.dot(wm)) * f, pdf)
+    }
+}
+
+/// Interpolates a constant given at red, green and blue to a wavelength in nanometers,
+/// taking 630, 532 and 465 nm as the wavelengths of the three channels.
+fn at_wavelength(color: Color, lambda: f32) -> f32 {
+    if lambda >= 532.0 {
+        let t = ((lambda - 532.0) / (630.0 - 532.0)).min(1.0);
+        color.g() + t * (color.r() - color.g())
+    } else {
+        let t = ((532.0 - lambda) / (532.0 - 465.0)).min(1.0);
+        color.g() + t * (color.b() - color.g())
+    }
+}
+
+impl Material for Conductor<'_> {
+    fn is_dispersive(&self) -> bool {
+        self.thin_film.is_some()
+    }
+
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
+        let basis = Onb::from_w_and_tangent(hit_record.normal, hit_record.tangent);
+        let wo = basis.project(-ray.direction().normalized());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let (wi, attenuation, pdf) = if self.distribution.is_smooth() {
+            let wi = Vec3::new(-wo.x(), -wo.y(), wo.z());
+            (wi, self.fresnel(ray, hit_record, wo.z()), 0.0)
+        } else {
+            // With visible normal sampling, the microfacet distribution and the visibility
+            // from the outgoing direction cancel out of the weight.
+            let wm = self.distribution.sample_visible_normal(wo);
+            let wi = (-wo).reflect(wm);
+            if wi.z() <= 0.0 {
+                return None;
+            }
+
+            let fresnel = self.fresnel(ray, hit_record, wo.dot(wm));
+            let masking = self.distribution.g(wo, wi) / self.distribution.g1(wo);
+            let pdf = self.distribution.visible_normal_pdf(wo, wm) / (4.0 * wo.dot(wm));
+            (wi, fresnel * masking, pdf)
+        };
+
+        Some(ScatterRecord {
+            attenuation,
+            scattered: Ray::new_time_based(hit_record.point, basis.local(wi), ray.time()),
+            pdf,
+        })
+    }
+
+    fn eval(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        let basis = Onb::from_w_and_tangent(hit_record.normal, hit_record.tangent);
+        let wo = basis.project(-ray.direction().normalized());
+        let wi = basis.project(direction.normalized());
+        self.evaluate(ray, hit_record, wo, wi).0
+    }
+
+    fn pdf(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        let basis = Onb::from_w_and_tangent(hit_record.normal, hit_record.tangent);
+        let wo = basis.project(-ray.direction().normalized());
+        let wi = basis.project(direction.normalized());
+        self.evaluate(ray, hit_record, wo, wi).1
+    }
+}
@@ -1,42 +1,330 @@
-use crate::{color::Color, objects::HitRecord, ray::Ray};
+use crate::{color::Color, medium::Medium, objects::HitRecord, ray::Ray, vec3::Vec3};
 
-use super::{Material, ScatterRecord};
+use super::{fresnel, microfacet::TrowbridgeReitz, Material, Onb, ScatterRecord, ThinFilm};
 
-#[derive(Debug, Clone)]
-pub struct Dielectric {
+/// Wavelength in nanometers at which dispersive indices of refraction are evaluated when
+/// rendering in RGB, the sodium D line.
+const RGB_WAVELENGTH: f32 = 587.6;
+
+/// Index of refraction, optionally depending on the wavelength of light.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ior {
+    Constant(f32),
+    /// Cauchy's equation `n = a + b / λ²`, with the wavelength in micrometers.
+    Cauchy {
+        a: f32,
+        b: f32,
+    },
+    /// Sellmeier equation `n² = 1 + Σ b λ² / (λ² - c)`, with the wavelength in micrometers.
+    Sellmeier {
+        b: [f32; 3],
+        c: [f32; 3],
+    },
+}
+
+impl Ior {
+    /// Schott N-BK7, the most common optical glass.
+    pub const BK7: Self = Self::Sellmeier {
+        b: [1.039_612, 0.231_792_3, 1.010_469_5],
+        c: [0.006_000_699, 0.020_017_914, 103.560_65],
+    };
+    /// Schott SF11, a dense flint glass with strong dispersion.
+    pub const SF11: Self = Self::Sellmeier {
+        b: [1.737_597, 0.313_747_35, 1.898_781],
+        c: [0.013_188_707, 0.062_306_814, 155.236_3],
+    };
+    /// Fused silica, a fit that is accurate across the visible range.
+    pub const FUSED_SILICA: Self = Self::Cauchy {
+        a: 1.458,
+        b: 0.003_54,
+    };
+    pub const DIAMOND: Self = Self::Sellmeier {
+        b: [0.3306, 4.3356, 0.0],
+        c: [0.030_625, 0.011_236, 0.0],
+    };
+
+    /// Evaluates the index of refraction at a wavelength in nanometers.
+    pub fn at(&self, wavelength: f32) -> f32 {
+        let micrometers = wavelength / 1000.0;
+        let lambda2 = micrometers * micrometers;
+        match *self {
+            Ior::Constant(n) => n,
+            Ior::Cauchy { a, b } => a + b / lambda2,
+            Ior::Sellmeier { b, c } => (1.0
+                + (0..3)
+                    .map(|i| b[i] * lambda2 / (lambda2 - c[i]))
+                    .sum::<f32>())
+            .sqrt(),
+        }
+    }
+
+    pub fn is_dispersive(&self) -> bool {
+        !matches!(self, Ior::Constant(_))
+    }
+}
+
+#[derive(Clone)]
+pub struct Dielectric<'a> {
     attenuation: Color,
-    refraction_index: f32,
+    ior: Ior,
+    distribution: TrowbridgeReitz,
+    medium: Option<Medium>,
+    thin_film: Option<ThinFilm<'a>>,
 }
 
-impl Dielectric {
+impl<'a> Dielectric<'a> {
     pub fn new(refraction_index: f32) -> Self {
+        Self::from_ior(Ior::Constant(refraction_index))
+    }
+
+    /// Creates a clear dielectric whose index of refraction may depend on the wavelength,
+    /// which splits white light into its colors when rendering spectrally.
+    pub fn from_ior(ior: Ior) -> Self {
         Self {
             attenuation: Color::new(1.0, 1.0, 1.0),
-            refraction_index,
+            ior,
+            distribution: TrowbridgeReitz::new(0.0, 0.0),
+            medium: None,
+            thin_film: None,
         }
     }
 
     pub fn from_color(color: Color, refraction_index: f32) -> Self {
         Self {
             attenuation: color,
-            refraction_index,
+            ior: Ior::Constant(refraction_index),
+            distribution: TrowbridgeReitz::new(0.0, 0.0),
+            medium: None,
+            thin_film: None,
+        }
+    }
+
+    /// Roughens the surface with GGX microfacets in `0..=1`, for frosted or ground glass.
+    pub fn with_roughness(self, roughness: f32) -> Self {
+        Self {
+            distribution: TrowbridgeReitz::new(roughness, roughness),
+            ..self
+        }
+    }
+
+    /// Absorbs light inside the object, so white light turns into `color` after travelling
+    /// `distance` through it. Unlike the color passed to `from_color`, thick parts of the
+    /// object end up darker than thin ones.
+    pub fn with_absorption(self, color: Color, distance: f32) -> Self {
+        Self {
+            medium: Some(Medium::absorbing(color, distance)),
+            ..self
         }
     }
 
+    /// Covers the surface with a thin film, for soap bubbles or coated lenses. A bubble is a
+    /// film with air on both sides, so its dielectric has an index of refraction of one.
+    pub fn with_thin_film(self, thin_film: ThinFilm<'a>) -> Self {
+        Self {
+            thin_film: Some(thin_film),
+            ..self
+        }
+    }
+
+    fn refraction_index(&self, ray: &Ray) -> f32 {
+        self.ior.at(ray.wavelength().unwrap_or(RGB_WAVELENGTH))
+    }
+
     fn reflectance(cosine: f32, refraction_index: f32) -> f32 {
         // Use Schlick's approximation for reflectance.
         let r0 = (1.0 - refraction_index) / (1.0 + refraction_index);
         let r0 = r0 * r0;
         r0 + (1.0 - r0) * (1.0 - cosine).powi(5)
     }
+
+    /// Reflectance of the thin film, if any, for light arriving with the cosine `cosine`
+    /// on the side of the surface the hit record faces.
+    fn film_reflectance(
+        &self,
+        ray: &Ray,
+        rec: &HitRecord,
+        cosine: f32,
+        refraction_index: f32,
+    ) -> Option<Color> {
+        let (outside, inside) = if rec.front_face {
+            (1.0, refraction_index)
+        } else {
+            (refraction_index, 1.0)
+        };
+        self.thin_film
+            .as_ref()
+            .map(|film| film.reflectance(ray, rec, cosine, outside, |_| (inside, 0.0)))
+    }
+
+    /// Chooses between reflection and transmission for a reflectance that depends on the
+    /// color. Returns whether to reflect, along with the weight of the choice.
+    fn choose_reflection(reflectance: Color) -> (bool, Color) {
+        let probability = (reflectance.r() + reflectance.g() + reflectance.b()) / 3.0;
+        if rand::random::<f32>() < probability {
+            (true, reflectance / probability)
+        } else {
+            (false, reflectance.map(|r| 1.0 - r) / (1.0 - probability))
+        }
+    }
+
+    /// Samples reflection or transmission through a rough interface.
+    /// Reference: Walter et al., "Microfacet Models for Refraction through Rough Surfaces" (2007).
+    fn scatter_rough(&self, ray: &Ray, rec: &HitRecord) -> Option<ScatterRecord> {
+        // Index of refraction of the far side over that of the side the ray comes from
+        let refraction_index = self.refraction_index(ray);
+        let eta = if rec.front_face {
+            refraction_index
+        } else {
+            1.0 / refraction_index
+        };
+
+        let basis = Onb::from_w_and_tangent(rec.normal, rec.tangent);
+        let wo = basis.project(-ray.direction().normalized());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let wm = self.distribution.sample_visible_normal(wo);
+        let (reflect, _) = match self.film_reflectance(ray, rec, wo.dot(wm), refraction_index) {
+            Some(reflectance) => Self::choose_reflection(reflectance),
+            None => (
+                rand::random::<f32>() < fresnel::dielectric(wo.dot(wm), eta),
+                Color::new(1.0, 1.0, 1.0),
+            ),
+        };
+        let wi = if reflect {
+            let wi = (-wo).reflect(wm);
+            if wi.z() <= 0.0 {
+                return None;
+            }
+            wi
+        } else {
+            let wi = fresnel::refract(wo, wm, eta)?;
+            if wi.z() >= 0.0 {
+                return None;
+            }
+            wi
+        };
+
+        let (f, pdf) = self.evaluate_rough(ray, rec, wo, wi);
+        if pdf <= 0.0 {
+            return None;
+        }
+
+        Some(ScatterRecord {
+            attenuation: f / pdf,
+            scattered: Ray::new_time_based(rec.point, basis.local(wi), ray.time()),
+            pdf,
+        })
+    }
+
+    /// BSDF of a rough interface times the cosine of `wi`, and the density with which
+    /// `scatter_rough` samples it, for local directions. With visible normal sampling and
+    /// choosing between reflection and transmission by the Fresnel term, only the masking of
+    /// the incoming direction remains in their ratio.
+    fn evaluate_rough(&self, ray: &Ray, rec: &HitRecord, wo: Vec3, wi: Vec3) -> (Color, f32) {
+        let black = (Color::new(0.0, 0.0, 0.0), 0.0);
+        if wo.z() <= 0.0 || wi.z() == 0.0 {
+            return black;
+        }
+
+        let refraction_index = self.refraction_index(ray);
+        let eta = if rec.front_face {
+            refraction_index
+        } else {
+            1.0 / refraction_index
+        };
+
+        // Microfacet normal that turns `wo` into `wi`, and the Jacobian of the change of
+        // variables from it to `wi`
+        let reflect = wi.z() > 0.0;
+        let (wm, dwm_dwi) = if reflect {
+            let wm = (wo + wi).normalized();
+            (wm, 1.0 / (4.0 * wo.dot(wm).abs()))
+        } else {
+            let wm = wo + wi * eta;
+            if wm.near_zero() {
+                return black;
+            }
+            let wm = wm.normalized();
+            let wm = if wm.z() < 0.0 { -wm } else { wm };
+            let denominator = wi.dot(wm) + wo.dot(wm) / eta;
+            (wm, wi.dot(wm).abs() / (denominator * denominator))
+        };
+
+        // Discard back-facing microfacets
+        if wo.dot(wm) <= 0.0 || (wi.dot(wm) > 0.0) != reflect {
+            return black;
+        }
+
+        let (reflectance, probability) =
+            match self.film_reflectance(ray, rec, wo.dot(wm), refraction_index) {
+                Some(reflectance) => (
+                    reflectance,
+                    (reflectance.r() + reflectance.g() + reflectance.b()) / 3.0,
+                ),
+                None => {
+                    let fresnel = fresnel::dielectric(wo.dot(wm), eta);
+                    (Color::new(fresnel, fresnel, fresnel), fresnel)
+                }
+            };
+        let (weight, probability) = if reflect {
+            (reflectance, probability)
+        } else {
+            (reflectance.map(|r| 1.0 - r), 1.0 - probability)
+        };
+
+        let d = self.distribution.d(wm);
+        let f = d * self.distribution.g(wo, wi) * wo.dot(wm) * dwm_dwi / wo.z();
+        let pdf = self.distribution.visible_normal_pdf(wo, wm) * dwm_dwi * probability;
+        (self.attenuation * weight * f, pdf)
+    }
+
+    fn local_directions(ray: &Ray, rec: &HitRecord, direction: Vec3) -> (Vec3, Vec3) {
+        let basis = Onb::from_w_and_tangent(rec.normal, rec.tangent);
+        (
+            basis.project(-ray.direction().normalized()),
+            basis.project(direction.normalized()),
+        )
+    }
 }
 
-impl Material for Dielectric {
+impl Material for Dielectric<'_> {
+    fn medium(&self) -> Option<Medium> {
+        self.medium
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.ior.is_dispersive() || self.thin_film.is_some()
+    }
+
+    /// Smooth glass only scatters into discrete directions, so it is black.
+    fn eval(&self, ray: &Ray, rec: &HitRecord, direction: Vec3) -> Color {
+        if self.distribution.is_smooth() {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let (wo, wi) = Self::local_directions(ray, rec, direction);
+        self.evaluate_rough(ray, rec, wo, wi).0
+    }
+
+    fn pdf(&self, ray: &Ray, rec: &HitRecord, direction: Vec3) -> f32 {
+        if self.distribution.is_smooth() {
+            return 0.0;
+        }
+        let (wo, wi) = Self::local_directions(ray, rec, direction);
+        self.evaluate_rough(ray, rec, wo, wi).1
+    }
+
     fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<ScatterRecord> {
+        if !self.distribution.is_smooth() {
+            return self.scatter_rough(ray, rec);
+        }
+
+        let refraction_index = self.refraction_index(ray);
         let etai_over_etat = if rec.front_face {
-            1.0 / self.refraction_index
+            1.0 / refraction_index
         } else {
-            self.refraction_index
+            refraction_index
         };
 
         let unit_direction = ray.direction().normalized();
@@ -45,9 +333,16 @@ impl Material for Dielectric {
 
         // Check for total internal reflection
         let cannot_refract = etai_over_etat * sin_theta > 1.0;
-        let reflactance_too_low = Self::reflectance(cos_theta, etai_over_etat) > rand::random();
+        let (reflect, weight) = match self.film_reflectance(ray, rec, cos_theta, refraction_index) {
+            _ if cannot_refract => (true, Color::new(1.0, 1.0, 1.0)),
+            Some(reflectance) => Self::choose_reflection(reflectance),
+            None => (
+                Self::reflectance(cos_theta, etai_over_etat) > rand::random(),
+                Color::new(1.0, 1.0, 1.0),
+            ),
+        };
 
-        let direction = if cannot_refract || reflactance_too_low {
+        let direction = if reflect {
             unit_direction.reflect(rec.normal)
         } else {
             unit_direction.refract(rec.normal, etai_over_etat)
@@ -56,8 +351,9 @@ impl Material for Dielectric {
         let scattered = Ray::new_time_based(rec.point, direction, ray.time());
 
         Some(ScatterRecord {
-            attenuation: self.attenuation,
+            attenuation: self.attenuation * weight,
             scattered,
+            pdf: 0.0,
         })
     }
 }
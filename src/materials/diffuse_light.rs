@@ -1,26 +1,97 @@
+use std::{f32::consts::PI, sync::Arc};
+
 use crate::{
+    color::Color,
+    ies::IesProfile,
+    objects::HitRecord,
+    ray::Ray,
     textures::{self, BoxedTexture, Texture},
-    vec3::Vec3,
+    vec3::Point3,
 };
 
-use super::Material;
+use super::{Material, Onb};
+
+/// Luminous efficacy of the light the eye is most sensitive to, in lumens per watt.
+pub(crate) const LUMENS_PER_WATT: f32 = 683.0;
+
+/// Smallest cosine to the normal that profiles are divided by, which keeps the radiance
+/// finite when the surface is seen edge-on.
+const MIN_PROFILE_COSINE: f32 = 1e-2;
+
+/// Total power emitted by a light.
+#[derive(Debug, Clone, Copy)]
+pub enum LightPower {
+    Watts(f32),
+    /// Power weighted by the sensitivity of the eye.
+    Lumens(f32),
+}
 
 #[derive(Clone)]
 pub struct DiffuseLight<'a> {
     emit: BoxedTexture<'a>,
+    /// Power in watts per unit area for a texture that averages to one, which scales the
+    /// texture to a radiance.
+    power: Option<f32>,
+    two_sided: bool,
+    /// Angular distribution of the emission, with the nadir along the normal.
+    profile: Option<Arc<IesProfile>>,
 }
 
 impl<'a> DiffuseLight<'a> {
-    #[allow(dead_code)]
     pub fn from_texture(emit: impl Texture + Send + Sync + 'a) -> Self {
         Self {
             emit: Box::new(emit),
+            power: None,
+            two_sided: true,
+            profile: None,
         }
     }
 
-    pub fn from_color(color: crate::color::Color) -> Self {
+    pub fn from_color(color: Color) -> Self {
+        Self::from_texture(textures::SolidColor::new(color))
+    }
+
+    /// Only emits light from the front face of the surface, the side its geometric normal
+    /// points to. Lights are two-sided by default.
+    pub fn one_sided(self) -> Self {
         Self {
-            emit: Box::new(textures::SolidColor::new(color)),
+            two_sided: false,
+            ..self
+        }
+    }
+
+    /// Color of the emitted texture at a point, ignoring the direction and power, which
+    /// area lights use to find the bright parts of the surface.
+    pub fn texture_color(&self, u: f32, v: f32, point: &Point3) -> Color {
+        self.emit.value(u, v, point)
+    }
+
+    /// Scales the emission so that a surface with the area `area`, over which the texture
+    /// averages to `average`, emits `power` in total. Returns `None` for a black texture,
+    /// which no scale brings to the power.
+    pub(crate) fn with_power(self, power: LightPower, average: Color, area: f32) -> Option<Self> {
+        let (norm, watts) = match power {
+            LightPower::Watts(watts) => ((average.r() + average.g() + average.b()) / 3.0, watts),
+            LightPower::Lumens(lumens) => (average.luminance(), lumens / LUMENS_PER_WATT),
+        };
+        if norm <= 0.0 || !norm.is_finite() {
+            return None;
+        }
+
+        Some(Self {
+            power: Some(watts / (norm * area)),
+            ..self
+        })
+    }
+
+    /// Shapes the emission by a photometric profile, with the nadir along the normal and
+    /// horizontal angles starting along the tangent. The intensity of the surface follows
+    /// the profile, scaled so the brightest direction gets the full color times the area,
+    /// or so the total power stays the same for area lights given their power.
+    pub fn with_profile(self, profile: IesProfile) -> Self {
+        Self {
+            profile: Some(Arc::new(profile)),
+            ..self
         }
     }
 }
@@ -34,7 +105,33 @@ impl Material for DiffuseLight<'_> {
         None
     }
 
-    fn emitted(&self, u: f32, v: f32, point: &Vec3) -> crate::color::Color {
-        self.emit.value(u, v, point)
+    fn emitted(&self, ray: &Ray, hit_record: &HitRecord) -> Color {
+        if !self.two_sided && !hit_record.front_face {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+
+        let emit = self.emit.at_hit(hit_record);
+        // The projected area of the surface already falls off with the cosine to the normal,
+        // so the radiance follows the profile divided by it for the intensity to match
+        let shape = self.profile.as_ref().map_or(1.0, |profile| {
+            let outgoing = -ray.direction().normalized();
+            let basis = Onb::from_w_and_tangent(hit_record.normal, hit_record.tangent);
+            let cosine = outgoing.dot(hit_record.normal).max(MIN_PROFILE_COSINE);
+            profile.relative_intensity(outgoing, &basis) / cosine
+        });
+
+        match self.power {
+            // A diffuse emitter with radiance L emits π L per unit area on each side. With a
+            // profile, the radiance times the cosine integrates to the profile instead.
+            Some(watts_per_area) => {
+                let sides = if self.two_sided { 2.0 } else { 1.0 };
+                let spread = self
+                    .profile
+                    .as_ref()
+                    .map_or(PI, |profile| profile.intensity_integral());
+                emit * (shape * watts_per_area / (spread * sides))
+            }
+            None => emit * shape,
+        }
     }
 }
@@ -0,0 +1,67 @@
+use crate::{color::Color, vec3::Vec3};
+
+/// Fresnel reflectance of a conductor with the complex index of refraction `eta + i k`,
+/// evaluated per color channel for unpolarized light.
+/// Reference: Lagarde, "Memo on Fresnel equations" (2013).
+pub fn conductor(cos_theta_i: f32, eta: Color, k: Color) -> Color {
+    let reflectance = |eta: f32, k: f32| {
+        let cos2 = cos_theta_i * cos_theta_i;
+        let sin2 = 1.0 - cos2;
+
+        let t0 = eta * eta - k * k - sin2;
+        let a2_plus_b2 = (t0 * t0 + 4.0 * eta * eta * k * k).sqrt();
+        let a = (0.5 * (a2_plus_b2 + t0)).max(0.0).sqrt();
+
+        let t1 = a2_plus_b2 + cos2;
+        let t2 = 2.0 * a * cos_theta_i;
+        let rs = (t1 - t2) / (t1 + t2);
+
+        let t3 = cos2 * a2_plus_b2 + sin2 * sin2;
+        let t4 = t2 * sin2;
+        let rp = rs * (t3 - t4) / (t3 + t4);
+
+        (0.5 * (rp + rs)).clamp(0.0, 1.0)
+    };
+
+    Color::new(
+        reflectance(eta.r(), k.r()),
+        reflectance(eta.g(), k.g()),
+        reflectance(eta.b(), k.b()),
+    )
+}
+
+/// Exact Fresnel reflectance of a dielectric interface for unpolarized light, where `eta` is
+/// the index of refraction of the far side over that of the near side. A negative cosine
+/// means the light arrives from the far side.
+pub fn dielectric(cos_theta_i: f32, eta: f32) -> f32 {
+    let (cos_theta_i, eta) = if cos_theta_i < 0.0 {
+        (-cos_theta_i, 1.0 / eta)
+    } else {
+        (cos_theta_i, eta)
+    };
+
+    let sin2_theta_t = (1.0 - cos_theta_i * cos_theta_i) / (eta * eta);
+    if sin2_theta_t >= 1.0 {
+        // Total internal reflection
+        return 1.0;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+
+    let r_parallel = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+    let r_perpendicular = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+    0.5 * (r_parallel * r_parallel + r_perpendicular * r_perpendicular)
+}
+
+/// Refracts `wi`, which points away from the surface on the same side as `normal`, through
+/// an interface with the relative index of refraction `eta`. Returns `None` on total
+/// internal reflection.
+pub fn refract(wi: Vec3, normal: Vec3, eta: f32) -> Option<Vec3> {
+    let cos_theta_i = normal.dot(wi);
+    let sin2_theta_t = (1.0 - cos_theta_i * cos_theta_i).max(0.0) / (eta * eta);
+    if sin2_theta_t >= 1.0 {
+        return None;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+
+    Some(-wi / eta + (cos_theta_i / eta - cos_theta_t) * normal)
+}
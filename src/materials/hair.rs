@@ -0,0 +1,152 @@
+use std::f32::consts::PI;
+
+use crate::{
+    color::Color,
+    objects::HitRecord,
+    rand_ext::rand,
+    ray::Ray,
+    textures::{BoxedTexture, SolidColor, Texture},
+    vec3::Vec3,
+};
+
+use super::{onb::Onb, Material, ScatterRecord};
+
+/// Angle by which the cuticle scales on a hair fiber are tilted towards the tip.
+const CUTICLE_TILT: f32 = 3.0 * PI / 180.0;
+
+/// Scattering model for hair and fur fibers, which uses `HitRecord::tangent` as the fiber
+/// direction.
+///
+/// A fraction of the light given by `specular` is reflected off the fiber's surface into a
+/// cone around the fiber, like the `R` lobe of Marschner et al. The angular spread of the cone is
+/// controlled by `roughness`, in radians. The rest is scattered with Kajiya and Kay's diffuse term.
+#[derive(Clone)]
+pub struct Hair<'a> {
+    albedo: BoxedTexture<'a>,
+    specular: f32,
+    roughness: f32,
+}
+
+impl<'a> Hair<'a> {
+    pub fn new(albedo: Color, specular: f32, roughness: f32) -> Self {
+        Self::from_texture(SolidColor::new(albedo), specular, roughness)
+    }
+
+    pub fn from_texture(
+        albedo: impl Texture + Send + Sync + 'a,
+        specular: f32,
+        roughness: f32,
+    ) -> Self {
+        Self {
+            albedo: Box::new(albedo),
+            specular: specular.clamp(0.0, 1.0),
+            roughness,
+        }
+    }
+
+    /// The reflection leaves at the mirrored angle to the fiber, shifted by the cuticle tilt.
+    fn reflection_angle(fiber: &Onb, view: Vec3) -> f32 {
+        -view.dot(fiber.w).clamp(-1.0, 1.0).asin() + 2.0 * CUTICLE_TILT
+    }
+
+    fn sample_reflection(&self, fiber: &Onb, view: Vec3) -> Vec3 {
+        // Spread out around the reflection angle by the roughness
+        let theta = (Self::reflection_angle(fiber, view)
+            + self.roughness * rand::standard_normal())
+        .clamp(-0.5 * PI, 0.5 * PI);
+        let phi = 2.0 * PI * rand::random::<f32>();
+
+        fiber.local(Vec3::new(
+            theta.cos() * phi.cos(),
+            theta.cos() * phi.sin(),
+            theta.sin(),
+        ))
+    }
+
+    /// Density over solid angle of `sample_reflection` picking `direction`, which is the
+    /// normal distribution of the angle to the fiber spread around the circle of directions
+    /// at that angle.
+    fn reflection_pdf(&self, fiber: &Onb, view: Vec3, direction: Vec3) -> f32 {
+        let theta = direction.dot(fiber.w).clamp(-1.0, 1.0).asin();
+        if theta.cos() < 1e-4 {
+            return 0.0;
+        }
+
+        let offset = (theta - Self::reflection_angle(fiber, view)) / self.roughness;
+        let normal = (-0.5 * offset * offset).exp() / ((2.0 * PI).sqrt() * self.roughness);
+        normal / (2.0 * PI * theta.cos())
+    }
+
+    /// BSDF times the cosine of `direction`, and the density with which `scatter` picks it,
+    /// leaving out a perfectly smooth reflection.
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> (Color, f32) {
+        let fiber = fiber(hit_record);
+        let view = -ray.direction().normalized();
+        let direction = direction.normalized();
+
+        // The reflection has a weight of one
+        let reflection = if self.roughness > 0.0 {
+            self.specular * self.reflection_pdf(&fiber, view, direction)
+        } else {
+            0.0
+        };
+
+        // Kajiya-Kay diffuse is proportional to the sine of the angle to the fiber, normalized
+        // to reflect all light, and sampled uniformly over the sphere
+        let diffuse = 1.0 - self.specular;
+        let sin_fiber = (1.0 - direction.dot(fiber.w).powi(2)).max(0.0).sqrt();
+        let albedo = self
+            .albedo
+            .value(hit_record.u, hit_record.v, &hit_record.point);
+
+        let f = Color::new(1.0, 1.0, 1.0) * reflection + albedo * (diffuse * sin_fiber / (PI * PI));
+        (f, reflection + diffuse / (4.0 * PI))
+    }
+}
+
+/// Frame around the fiber direction.
+fn fiber(hit_record: &HitRecord) -> Onb {
+    let tangent = if hit_record.tangent.near_zero() {
+        Onb::from_w(hit_record.normal).u
+    } else {
+        hit_record.tangent
+    };
+    Onb::from_w(tangent)
+}
+
+impl Material for Hair<'_> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
+        let reflect = rand::random::<f32>() < self.specular;
+        let direction = if reflect {
+            let view = -ray.direction().normalized();
+            self.sample_reflection(&fiber(hit_record), view)
+        } else {
+            Vec3::random_on_unit_sphere()
+        };
+
+        // A smooth reflection is a discrete direction that the other lobe can't pick
+        let (attenuation, pdf) = if reflect && self.roughness <= 0.0 {
+            (Color::new(1.0, 1.0, 1.0), 0.0)
+        } else {
+            let (f, pdf) = self.evaluate(ray, hit_record, direction);
+            if pdf <= 0.0 {
+                return None;
+            }
+            (f / pdf, pdf)
+        };
+
+        Some(ScatterRecord {
+            attenuation,
+            scattered: Ray::new_time_based(hit_record.point, direction, ray.time()),
+            pdf,
+        })
+    }
+
+    fn eval(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        self.evaluate(ray, hit_record, direction).0
+    }
+
+    fn pdf(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        self.evaluate(ray, hit_record, direction).1
+    }
+}
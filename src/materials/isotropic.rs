@@ -1,3 +1,5 @@
+use std::f32::consts::PI;
+
 use crate::{
     color::Color,
     objects::HitRecord,
@@ -38,6 +40,18 @@ impl Material for Isotropic<'_> {
         Some(ScatterRecord {
             attenuation,
             scattered,
+            pdf: 1.0 / (4.0 * PI),
         })
     }
+
+    /// Scatters equally into all directions, so there is no cosine with a surface.
+    fn eval(&self, _ray: &Ray, hit_record: &HitRecord, _direction: Vec3) -> Color {
+        self.albedo
+            .value(hit_record.u, hit_record.v, &hit_record.point)
+            / (4.0 * PI)
+    }
+
+    fn pdf(&self, _ray: &Ray, _hit_record: &HitRecord, _direction: Vec3) -> f32 {
+        1.0 / (4.0 * PI)
+    }
 }
@@ -1,11 +1,14 @@
+use std::f32::consts::PI;
+
 use crate::{
     color::Color,
     objects::HitRecord,
     ray::Ray,
     textures::{BoxedTexture, SolidColor, Texture},
+    vec3::Vec3,
 };
 
-use super::{diffusers, Material, ScatterRecord};
+use super::{diffusers, Material, Onb, ScatterRecord};
 
 #[derive(Clone)]
 pub struct Lambertian<'a> {
@@ -28,20 +31,24 @@ impl<'a> Lambertian<'a> {
 
 impl Material for Lambertian<'_> {
     fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
-        let mut scatter_direction =
-            hit_record.normal + diffusers::random_lambertian(&hit_record.normal);
-
-        if scatter_direction.near_zero() {
-            scatter_direction = hit_record.normal;
-        }
-
+        // Cosine-weighted sampling cancels out the cosine and π of the BRDF
+        let scatter_direction =
+            Onb::from_w(hit_record.normal).local(diffusers::random_cosine_direction());
         let scattered = Ray::new_time_based(hit_record.point, scatter_direction, ray.time());
 
         Some(ScatterRecord {
-            attenuation: self
-                .texture
-                .value(hit_record.u, hit_record.v, &hit_record.point),
+            attenuation: self.texture.at_hit(hit_record),
             scattered,
+            pdf: self.pdf(ray, hit_record, scatter_direction),
         })
     }
+
+    fn eval(&self, _ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        let cos_theta = direction.normalized().dot(hit_record.normal).max(0.0);
+        self.texture.at_hit(hit_record) * (cos_theta / PI)
+    }
+
+    fn pdf(&self, _ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        direction.normalized().dot(hit_record.normal).max(0.0) / PI
+    }
 }
@@ -2,6 +2,12 @@ use crate::{color::Color, objects::HitRecord, ray::Ray, vec3::Vec3};
 
 use super::{Material, ScatterRecord};
 
+/// Metal whose reflection is blurred by jittering the mirror direction within a sphere of
+/// radius `fuzziness`.
+///
+/// The jittered directions have no closed-form density, so light sampling leaves the metal
+/// out. It only reflects light that scattered rays find, which leaves it dark under point,
+/// spot and directional lights. `Conductor` reflects those too.
 #[derive(Debug, Clone)]
 pub struct Metal {
     albedo: Color,
@@ -24,6 +30,7 @@ impl Material for Metal {
             Some(ScatterRecord {
                 attenuation: self.albedo,
                 scattered,
+                pdf: 0.0,
             })
         } else {
             None
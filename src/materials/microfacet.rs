@@ -0,0 +1,108 @@
+use std::f32::consts::PI;
+
+use crate::vec3::Vec3;
+
+/// Below this roughness the surface is treated as a perfect mirror.
+const SMOOTH_ALPHA: f32 = 1e-3;
+
+/// Trowbridge-Reitz (GGX) distribution of microfacet normals, in a local frame where the
+/// surface normal is the z-axis and `alpha_x` is the roughness along the tangent.
+#[derive(Debug, Clone, Copy)]
+pub struct TrowbridgeReitz {
+    alpha_x: f32,
+    alpha_y: f32,
+}
+
+impl TrowbridgeReitz {
+    /// Maps perceptual roughness in `0..=1` along the tangent and bitangent to the
+    /// distribution's alpha, which is the square of it.
+    pub fn new(roughness_x: f32, roughness_y: f32) -> Self {
+        Self {
+            alpha_x: roughness_x.clamp(0.0, 1.0).powi(2),
+            alpha_y: roughness_y.clamp(0.0, 1.0).powi(2),
+        }
+    }
+
+    pub fn is_smooth(&self) -> bool {
+        self.alpha_x.max(self.alpha_y) < SMOOTH_ALPHA
+    }
+
+    /// Density of microfacets with the normal `wm`.
+    pub fn d(&self, wm: Vec3) -> f32 {
+        if wm.z() <= 0.0 {
+            return 0.0;
+        }
+
+        let cos2 = wm.z() * wm.z();
+        let e = (wm.x() * wm.x() / (self.alpha_x * self.alpha_x)
+            + wm.y() * wm.y() / (self.alpha_y * self.alpha_y))
+            / cos2;
+        1.0 / (PI * self.alpha_x * self.alpha_y * cos2 * cos2 * (1.0 + e) * (1.0 + e))
+    }
+
+    /// Probability density of `sample_visible_normal` returning `wm` when seen from `w`.
+    pub fn visible_normal_pdf(&self, w: Vec3, wm: Vec3) -> f32 {
+        if w.z() <= 0.0 {
+            return 0.0;
+        }
+
+        self.g1(w) / w.z() * self.d(wm) * w.dot(wm).max(0.0)
+    }
+
+    /// Smith's auxiliary function, which measures the microfacet area hidden from `w`.
+    fn lambda(&self, w: Vec3) -> f32 {
+        let cos2 = w.z() * w.z();
+        if cos2 == 0.0 {
+            return f32::INFINITY;
+        }
+
+        let alpha2_tan2 = (self.alpha_x * self.alpha_x * w.x() * w.x()
+            + self.alpha_y * self.alpha_y * w.y() * w.y())
+            / cos2;
+        0.5 * ((1.0 + alpha2_tan2).sqrt() - 1.0)
+    }
+
+    /// Fraction of microfacets visible from `w`.
+    pub fn g1(&self, w: Vec3) -> f32 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    /// Fraction of microfacets visible from both `wo` and `wi`.
+    pub fn g(&self, wo: Vec3, wi: Vec3) -> f32 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /// Samples a microfacet normal from the normals visible from `w`, which has to lie in
+    /// the upper hemisphere.
+    /// Reference: Heitz, "Sampling the GGX Distribution of Visible Normals" (2018).
+    pub fn sample_visible_normal(&self, w: Vec3) -> Vec3 {
+        // Stretch the view direction so the distribution becomes the hemisphere
+        let wh = Vec3::new(self.alpha_x * w.x(), self.alpha_y * w.y(), w.z()).normalized();
+
+        let length2 = wh.x() * wh.x() + wh.y() * wh.y();
+        let t1 = if length2 > 0.0 {
+            Vec3::new(-wh.y(), wh.x(), 0.0) / length2.sqrt()
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let t2 = wh.cross(t1);
+
+        // Sample the projected area of the hemisphere as seen from the view direction
+        let (r1, r2): (f32, f32) = rand::random();
+        let r = r1.sqrt();
+        let phi = 2.0 * PI * r2;
+        let p1 = r * phi.cos();
+        let s = 0.5 * (1.0 + wh.z());
+        let p2 = (1.0 - s) * (1.0 - p1 * p1).sqrt() + s * r * phi.sin();
+        let p3 = (1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt();
+        let nh = p1 * t1 + p2 * t2 + p3 * wh;
+
+        // Unstretch back to the actual microfacet normal
+        Vec3::new(
+            self.alpha_x * nh.x(),
+            self.alpha_y * nh.y(),
+            nh.z().max(1e-6),
+        )
+        .normalized()
+    }
+}
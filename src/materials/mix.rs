@@ -0,0 +1,103 @@
+use crate::{
+    color::Color,
+    medium::Medium,
+    objects::HitRecord,
+    ray::Ray,
+    textures::{BoxedTexture, Texture},
+    vec3::Vec3,
+};
+
+use super::{BoxedMaterial, Material, ScatterRecord};
+
+/// Blends two materials by a weight read from the luminance of a texture, where 0 is
+/// entirely the first material and 1 entirely the second one.
+///
+/// Every scattering event picks one of the materials at random with the probability of its
+/// weight, which averages out to the blend of both. Emission is blended directly.
+#[derive(Clone)]
+pub struct MixMaterial<'a> {
+    first: BoxedMaterial<'a>,
+    second: BoxedMaterial<'a>,
+    weight: BoxedTexture<'a>,
+}
+
+impl<'a> MixMaterial<'a> {
+    pub fn new(
+        first: impl Material + Send + Sync + 'a,
+        second: impl Material + Send + Sync + 'a,
+        weight: impl Texture + Send + Sync + 'a,
+    ) -> Self {
+        Self::new_boxed(Box::new(first), Box::new(second), Box::new(weight))
+    }
+
+    pub fn new_boxed(
+        first: BoxedMaterial<'a>,
+        second: BoxedMaterial<'a>,
+        weight: BoxedTexture<'a>,
+    ) -> Self {
+        Self {
+            first,
+            second,
+            weight,
+        }
+    }
+
+    fn weight(&self, u: f32, v: f32, point: &Vec3) -> f32 {
+        self.weight.value(u, v, point).luminance().clamp(0.0, 1.0)
+    }
+
+    fn choose(&self, u: f32, v: f32, point: &Vec3) -> &BoxedMaterial<'a> {
+        if rand::random::<f32>() < self.weight(u, v, point) {
+            &self.second
+        } else {
+            &self.first
+        }
+    }
+}
+
+impl Material for MixMaterial<'_> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
+        let scatter = self
+            .choose(hit_record.u, hit_record.v, &hit_record.point)
+            .scatter(ray, hit_record)?;
+
+        // Either material could have sampled the direction, unless it was a mirror reflection
+        // or similar, which only the chosen one produces
+        let pdf = if scatter.pdf > 0.0 {
+            self.pdf(ray, hit_record, scatter.scattered.direction())
+        } else {
+            0.0
+        };
+        Some(ScatterRecord { pdf, ..scatter })
+    }
+
+    fn emitted(&self, ray: &Ray, hit_record: &HitRecord) -> Color {
+        let weight = self.weight(hit_record.u, hit_record.v, &hit_record.point);
+        (1.0 - weight) * self.first.emitted(ray, hit_record)
+            + weight * self.second.emitted(ray, hit_record)
+    }
+
+    fn eval(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        let weight = self.weight(hit_record.u, hit_record.v, &hit_record.point);
+        (1.0 - weight) * self.first.eval(ray, hit_record, direction)
+            + weight * self.second.eval(ray, hit_record, direction)
+    }
+
+    fn pdf(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        let weight = self.weight(hit_record.u, hit_record.v, &hit_record.point);
+        (1.0 - weight) * self.first.pdf(ray, hit_record, direction)
+            + weight * self.second.pdf(ray, hit_record, direction)
+    }
+
+    fn alpha_test(&self, u: f32, v: f32, point: &Vec3) -> bool {
+        self.choose(u, v, point).alpha_test(u, v, point)
+    }
+
+    fn medium(&self) -> Option<Medium> {
+        self.first.medium().or_else(|| self.second.medium())
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.first.is_dispersive() || self.second.is_dispersive()
+    }
+}
@@ -1,31 +1,97 @@
+mod alpha_cutout;
+mod coated;
+mod conductor;
 mod dielectric;
 mod diffuse_light;
 mod diffusers;
+mod fresnel;
+mod hair;
 mod isotropic;
 mod lambertian;
 mod metal;
+mod microfacet;
+mod mix;
+mod normal_mapped;
+mod onb;
+mod oren_nayar;
+mod principled;
+mod subsurface;
+#[cfg(test)]
+mod testing;
+mod thin_film;
 
 use dyn_clonable::clonable;
 
-pub use dielectric::Dielectric;
-pub use diffuse_light::DiffuseLight;
+pub use alpha_cutout::{AlphaCutout, AlphaMode};
+pub use coated::Coated;
+pub use conductor::Conductor;
+pub use dielectric::{Dielectric, Ior};
+pub(crate) use diffuse_light::LUMENS_PER_WATT;
+pub use diffuse_light::{DiffuseLight, LightPower};
+pub use hair::Hair;
 pub use isotropic::Isotropic;
 pub use lambertian::Lambertian;
 pub use metal::Metal;
+pub use mix::MixMaterial;
+pub use normal_mapped::NormalMapped;
+pub use onb::Onb;
+pub use oren_nayar::OrenNayar;
+pub use principled::Principled;
+pub use subsurface::Subsurface;
+pub use thin_film::ThinFilm;
 
-use crate::{color::Color, objects::HitRecord, ray::Ray, vec3::Vec3};
+use crate::{color::Color, medium::Medium, objects::HitRecord, ray::Ray, vec3::Vec3};
 
 pub struct ScatterRecord {
     pub attenuation: Color,
     pub scattered: Ray,
+    /// Density with which the scattered direction was sampled, as given by
+    /// [`Material::pdf`]. Zero when the direction came from somewhere else, such as a
+    /// mirror reflection.
+    pub pdf: f32,
 }
 
 #[clonable]
 pub trait Material: Clone {
     fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord>;
-    fn emitted(&self, _u: f32, _v: f32, _point: &Vec3) -> Color {
+    /// Light emitted from the surface at the hit, towards the incoming ray.
+    fn emitted(&self, _ray: &Ray, _hit_record: &HitRecord) -> Color {
         Color::new(0.0, 0.0, 0.0)
     }
+
+    /// Evaluates the BSDF times the cosine of `direction` with the normal, for light
+    /// arriving from `direction` and leaving against the incoming `ray`. Materials that only
+    /// scatter into discrete directions, such as mirrors, return black, and so do `Metal` and
+    /// `Coated`, whose scattering can't be evaluated. Light sampling skips black materials,
+    /// so they only reflect lights that scattered rays can hit, which excludes point, spot and
+    /// directional lights.
+    fn eval(&self, _ray: &Ray, _hit_record: &HitRecord, _direction: Vec3) -> Color {
+        Color::new(0.0, 0.0, 0.0)
+    }
+
+    /// Probability density per solid angle of `scatter` picking `direction`, zero for
+    /// discrete directions.
+    fn pdf(&self, _ray: &Ray, _hit_record: &HitRecord, _direction: Vec3) -> f32 {
+        0.0
+    }
+
+    /// Whether the surface exists at the given point. Intersection routines skip hits that
+    /// fail the test, which cuts holes into the geometry.
+    fn alpha_test(&self, _u: f32, _v: f32, _point: &Vec3) -> bool {
+        true
+    }
+
+    /// Medium filling objects with this material, which rays enter and leave by
+    /// transmitting through the surface.
+    fn medium(&self) -> Option<Medium> {
+        None
+    }
+
+    /// Whether the scattered direction depends on the wavelength, in which case a spectral
+    /// path can only carry on with a single wavelength.
+    fn is_dispersive(&self) -> bool {
+        false
+    }
 }
 
 pub type BoxedMaterial<'a> = Box<dyn Material + Send + Sync + 'a>;
@@ -0,0 +1,156 @@
+use crate::{
+    color::Color,
+    medium::Medium,
+    objects::HitRecord,
+    ray::Ray,
+    textures::{BoxedTexture, Texture},
+    vec3::Vec3,
+};
+
+use super::{BoxedMaterial, Material, ScatterRecord};
+
+/// Offset used for the finite differences of bump maps, in texture or world space.
+const BUMP_DELTA: f32 = 0.001;
+
+#[derive(Clone)]
+enum NormalMap<'a> {
+    /// Normals encoded in the texture's color, relative to the tangent frame.
+    TangentSpace(BoxedTexture<'a>),
+    /// Height given by the texture's luminance.
+    Bump {
+        height: BoxedTexture<'a>,
+        strength: f32,
+    },
+}
+
+/// Perturbs the shading normal of another material, using the tangent frame of the hit.
+#[derive(Clone)]
+pub struct NormalMapped<'a> {
+    base: BoxedMaterial<'a>,
+    map: NormalMap<'a>,
+}
+
+impl<'a> NormalMapped<'a> {
+    /// Uses a tangent-space normal map, as commonly stored in images: red, green and blue
+    /// map from `0..=1` to `-1..=1` along the tangent, bitangent and normal.
+    pub fn from_normal_map(
+        base: impl Material + Send + Sync + 'a,
+        normal_map: impl Texture + Send + Sync + 'a,
+    ) -> Self {
+        Self {
+            base: Box::new(base),
+            map: NormalMap::TangentSpace(Box::new(normal_map)),
+        }
+    }
+
+    /// Uses the luminance of any texture as a height map.
+    /// `strength` scales the slope of the surface, where the height changes per unit
+    /// of `u` and `v`, or of world space for textures that don't use texture coordinates.
+    pub fn from_bump_map(
+        base: impl Material + Send + Sync + 'a,
+        height: impl Texture + Send + Sync + 'a,
+        strength: f32,
+    ) -> Self {
+        Self {
+            base: Box::new(base),
+            map: NormalMap::Bump {
+                height: Box::new(height),
+                strength,
+            },
+        }
+    }
+
+    fn shading_normal(&self, hit_record: &HitRecord) -> Vec3 {
+        let (tangent, bitangent, normal) =
+            (hit_record.tangent, hit_record.bitangent, hit_record.normal);
+        if tangent.near_zero() || bitangent.near_zero() {
+            return normal;
+        }
+
+        let perturbed = match &self.map {
+            NormalMap::TangentSpace(texture) => {
+                let encoded = texture.value(hit_record.u, hit_record.v, &hit_record.point);
+                let local = Vec3::new(
+                    2.0 * encoded.r() - 1.0,
+                    2.0 * encoded.g() - 1.0,
+                    2.0 * encoded.b() - 1.0,
+                );
+                local.x() * tangent + local.y() * bitangent + local.z() * normal
+            }
+            NormalMap::Bump { height, strength } => {
+                let (u, v, point) = (hit_record.u, hit_record.v, hit_record.point);
+                let center = height.value(u, v, &point).luminance();
+                let along_u = height
+                    .value(u + BUMP_DELTA, v, &(point + BUMP_DELTA * tangent))
+                    .luminance();
+                let along_v = height
+                    .value(u, v + BUMP_DELTA, &(point + BUMP_DELTA * bitangent))
+                    .luminance();
+
+                let slope_u = strength * (along_u - center) / BUMP_DELTA;
+                let slope_v = strength * (along_v - center) / BUMP_DELTA;
+                normal - slope_u * tangent - slope_v * bitangent
+            }
+        };
+
+        if perturbed.near_zero() {
+            normal
+        } else {
+            perturbed.normalized()
+        }
+    }
+
+    /// Hit record with the perturbed normal, passed on to the base material.
+    fn shading_record<'r>(&'r self, hit_record: &HitRecord<'r>) -> HitRecord<'r> {
+        let normal = self.shading_normal(hit_record);
+
+        // Keep the tangent frame orthogonal to the new normal
+        let tangent = (hit_record.tangent - normal * normal.dot(hit_record.tangent)).normalized();
+        let bitangent = normal.cross(tangent);
+        let bitangent = if bitangent.dot(hit_record.bitangent) < 0.0 {
+            -bitangent
+        } else {
+            bitangent
+        };
+
+        HitRecord {
+            normal,
+            tangent,
+            bitangent,
+            material: self.base.as_ref(),
+            ..*hit_record
+        }
+    }
+}
+
+impl Material for NormalMapped<'_> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
+        self.base.scatter(ray, &self.shading_record(hit_record))
+    }
+
+    fn eval(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        self.base
+            .eval(ray, &self.shading_record(hit_record), direction)
+    }
+
+    fn pdf(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        self.base
+            .pdf(ray, &self.shading_record(hit_record), direction)
+    }
+
+    fn emitted(&self, ray: &Ray, hit_record: &HitRecord) -> Color {
+        self.base.emitted(ray, hit_record)
+    }
+
+    fn alpha_test(&self, u: f32, v: f32, point: &Vec3) -> bool {
+        self.base.alpha_test(u, v, point)
+    }
+
+    fn medium(&self) -> Option<Medium> {
+        self.base.medium()
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.base.is_dispersive()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::vec3::Vec3;
+
+/// Orthonormal basis with `w` pointing along a given direction.
+#[derive(Debug, Clone, Copy)]
+pub struct Onb {
+    pub u: Vec3,
+    pub v: Vec3,
+    pub w: Vec3,
+}
+
+impl Onb {
+    /// Builds an arbitrary basis around the unit vector `w`.
+    /// Reference: Duff et al., "Building an Orthonormal Basis, Revisited" (2017).
+    pub fn from_w(w: Vec3) -> Self {
+        let sign = 1f32.copysign(w.z());
+        let a = -1.0 / (sign + w.z());
+        let b = w.x() * w.y() * a;
+        let u = Vec3::new(1.0 + sign * w.x() * w.x() * a, sign * b, -sign * w.x());
+        let v = Vec3::new(b, sign + w.y() * w.y() * a, -w.y());
+
+        Self { u, v, w }
+    }
+
+    /// Builds a basis around the unit vector `w` whose `u` follows `tangent` as closely as
+    /// possible. Falls back to an arbitrary basis if the tangent is missing.
+    pub fn from_w_and_tangent(w: Vec3, tangent: Vec3) -> Self {
+        let u = tangent - w * w.dot(tangent);
+        if u.length_squared() < 1e-8 {
+            return Self::from_w(w);
+        }
+
+        let u = u.normalized();
+        Self {
+            u,
+            v: w.cross(u),
+            w,
+        }
+    }
+
+    /// Transforms a vector given in world space to this basis.
+    pub fn project(&self, a: Vec3) -> Vec3 {
+        Vec3::new(a.dot(self.u), a.dot(self.v), a.dot(self.w))
+    }
+
+    /// Transforms a vector given in this basis to world space.
+    pub fn local(&self, a: Vec3) -> Vec3 {
+        a.x() * self.u + a.y() * self.v + a.z() * self.w
+    }
+}
@@ -0,0 +1,118 @@
+use std::f32::consts::PI;
+
+use crate::{
+    color::Color,
+    objects::HitRecord,
+    ray::Ray,
+    textures::{BoxedTexture, SolidColor, Texture},
+    vec3::Vec3,
+};
+
+use super::{diffusers, Material, Onb, ScatterRecord};
+
+/// Constants of the Fujii Oren-Nayar model.
+const FON_A: f32 = 0.5 - 2.0 / (3.0 * PI);
+const FON_B: f32 = 2.0 / 3.0 - 28.0 / (15.0 * PI);
+
+/// Rough diffuse material, for surfaces such as clay, plaster or moon dust which look
+/// flatter than `Lambertian` and reflect more light back towards the light source.
+///
+/// Uses the energy-preserving Oren-Nayar model, which adds back the light lost to
+/// interreflections between the facets so that a white surface stays white at any
+/// roughness. The roughness in `0..=1` is read from the luminance of its texture, zero
+/// gives a Lambertian surface.
+/// Reference: Portsmouth et al., "EON: A practical energy-preserving rough diffuse BRDF" (2025).
+#[derive(Clone)]
+pub struct OrenNayar<'a> {
+    texture: BoxedTexture<'a>,
+    roughness: BoxedTexture<'a>,
+}
+
+impl<'a> OrenNayar<'a> {
+    pub fn new(albedo: Color) -> Self {
+        Self::from_texture(SolidColor::new(albedo))
+    }
+
+    pub fn from_texture(texture: impl Texture + Send + Sync + 'a) -> Self {
+        Self {
+            texture: Box::new(texture),
+            roughness: Box::new(1.0),
+        }
+    }
+
+    pub fn with_roughness(self, roughness: impl Texture + Send + Sync + 'a) -> Self {
+        Self {
+            roughness: Box::new(roughness),
+            ..self
+        }
+    }
+
+    /// Evaluates the BRDF for the directions `wo` and `wi` given in the local frame.
+    fn brdf(&self, hit_record: &HitRecord, wo: Vec3, wi: Vec3) -> Color {
+        let (u, v, point) = (hit_record.u, hit_record.v, &hit_record.point);
+        let albedo = self.texture.at_hit(hit_record);
+        let roughness = self
+            .roughness
+            .value(u, v, point)
+            .luminance()
+            .clamp(0.0, 1.0);
+
+        // Single scattering off the facets
+        let s = wi.dot(wo) - wi.z() * wo.z();
+        let s_over_t = if s > 0.0 { s / wi.z().max(wo.z()) } else { s };
+        let a = 1.0 / (1.0 + FON_A * roughness);
+        let single = albedo * (a * (1.0 + roughness * s_over_t) / PI);
+
+        // Multiple scattering, which makes up for the energy missing from the single
+        // scattering albedo in each direction
+        let average_albedo = a * (1.0 + FON_B * roughness);
+        let multiple_albedo =
+            albedo.map(|rho| rho * rho * average_albedo / (1.0 - rho * (1.0 - average_albedo)));
+        let missing = |cos_theta: f32| (1.0 - directional_albedo(cos_theta, roughness)).max(1e-7);
+        let multiple = multiple_albedo
+            * (missing(wo.z()) * missing(wi.z()) / (PI * (1.0 - average_albedo).max(1e-7)));
+
+        single + multiple
+    }
+}
+
+/// Albedo of the single scattering lobe lit from a direction with the given cosine, with a
+/// polynomial fit of the exact expression.
+fn directional_albedo(cos_theta: f32, roughness: f32) -> f32 {
+    let x = 1.0 - cos_theta;
+    let g_over_pi = x * (0.057_108_53 + x * (0.491_881_87 + x * (-0.332_181_44 + x * 0.071_443)));
+    (1.0 + roughness * g_over_pi) / (1.0 + FON_A * roughness)
+}
+
+impl Material for OrenNayar<'_> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
+        let basis = Onb::from_w(hit_record.normal);
+        let wo = basis.project(-ray.direction().normalized());
+        let wi = diffusers::random_cosine_direction();
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return None;
+        }
+
+        // The cosine and π cancel out with the sampling density
+        Some(ScatterRecord {
+            attenuation: self.brdf(hit_record, wo, wi) * PI,
+            scattered: Ray::new_time_based(hit_record.point, basis.local(wi), ray.time()),
+            pdf: wi.z() / PI,
+        })
+    }
+
+    fn eval(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        let basis = Onb::from_w(hit_record.normal);
+        let wo = basis.project(-ray.direction().normalized());
+        let wi = basis.project(direction.normalized());
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+
+        self.brdf(hit_record, wo, wi) * wi.z()
+    }
+
+    fn pdf(&self, _ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        direction.normalized().dot(hit_record.normal).max(0.0) / PI
+    }
+}
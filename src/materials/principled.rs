@@ -0,0 +1,402 @@
+use std::f32::consts::PI;
+
+use crate::{
+    color::Color,
+    objects::HitRecord,
+    ray::Ray,
+    textures::{BoxedTexture, Texture},
+    vec3::Vec3,
+};
+
+use super::{fresnel, microfacet::TrowbridgeReitz, Material, Onb, ScatterRecord};
+
+/// Lower bound on the roughness, which keeps every lobe sampleable by `pdf`.
+const MIN_ROUGHNESS: f32 = 0.05;
+/// Index of refraction of the clearcoat layer.
+const CLEARCOAT_IOR: f32 = 1.5;
+
+/// Principled material in the spirit of the Disney BSDF, which covers most real world
+/// surfaces with a handful of intuitive parameters in `0..=1`.
+///
+/// The lobes are stacked as layers: an optional clearcoat on top of a blend between metal
+/// and a dielectric base, which has a specular reflection over glass-like transmission or
+/// a diffuse lobe. Every layer only passes on the light it doesn't reflect, so the
+/// material never reflects more light than it receives. Scalar parameters are read from
+/// the luminance of their texture.
+///
+/// Subsurface scattering is approximated by diffusely transmitting part of the light
+/// through the surface, which makes thin objects such as leaves and paper translucent.
+#[derive(Clone)]
+pub struct Principled<'a> {
+    base_color: BoxedTexture<'a>,
+    metallic: BoxedTexture<'a>,
+    roughness: BoxedTexture<'a>,
+    specular: BoxedTexture<'a>,
+    clearcoat: BoxedTexture<'a>,
+    clearcoat_roughness: f32,
+    sheen: BoxedTexture<'a>,
+    transmission: BoxedTexture<'a>,
+    subsurface: BoxedTexture<'a>,
+}
+
+impl<'a> Principled<'a> {
+    /// Creates a rough dielectric with the given base color, the other parameters can be
+    /// set with the `with_*` methods.
+    pub fn new(base_color: impl Texture + Send + Sync + 'a) -> Self {
+        Self {
+            base_color: Box::new(base_color),
+            metallic: Box::new(0.0),
+            roughness: Box::new(0.5),
+            specular: Box::new(0.5),
+            clearcoat: Box::new(0.0),
+            clearcoat_roughness: 0.1,
+            sheen: Box::new(0.0),
+            transmission: Box::new(0.0),
+            subsurface: Box::new(0.0),
+        }
+    }
+
+    /// Blends from a dielectric to a metal, which reflects tinted by the base color.
+    pub fn with_metallic(self, metallic: impl Texture + Send + Sync + 'a) -> Self {
+        Self {
+            metallic: Box::new(metallic),
+            ..self
+        }
+    }
+
+    pub fn with_roughness(self, roughness: impl Texture + Send + Sync + 'a) -> Self {
+        Self {
+            roughness: Box::new(roughness),
+            ..self
+        }
+    }
+
+    /// Strength of the specular reflection of dielectrics, where 0.5 corresponds to an index
+    /// of refraction of 1.5 and 1 to about 1.8.
+    pub fn with_specular(self, specular: impl Texture + Send + Sync + 'a) -> Self {
+        Self {
+            specular: Box::new(specular),
+            ..self
+        }
+    }
+
+    /// Adds a clear varnish layer with its own roughness on top of the material.
+    pub fn with_clearcoat(
+        self,
+        clearcoat: impl Texture + Send + Sync + 'a,
+        roughness: f32,
+    ) -> Self {
+        Self {
+            clearcoat: Box::new(clearcoat),
+            clearcoat_roughness: roughness,
+            ..self
+        }
+    }
+
+    /// Tints the diffuse lobe towards white at grazing angles, as seen on cloth. This is a
+    /// tint rather than a lobe of its own, so it doesn't reflect light back towards grazing
+    /// viewers, and metals and glass, which have no diffuse lobe, show no sheen.
+    pub fn with_sheen(self, sheen: impl Texture + Send + Sync + 'a) -> Self {
+        Self {
+            sheen: Box::new(sheen),
+            ..self
+        }
+    }
+
+    /// Replaces the diffuse lobe by refraction through the surface, tinted by the base color.
+    pub fn with_transmission(self, transmission: impl Texture + Send + Sync + 'a) -> Self {
+        Self {
+            transmission: Box::new(transmission),
+            ..self
+        }
+    }
+
+    pub fn with_subsurface(self, subsurface: impl Texture + Send + Sync + 'a) -> Self {
+        Self {
+            subsurface: Box::new(subsurface),
+            ..self
+        }
+    }
+
+    fn lobes(&self, ray: &Ray, hit_record: &HitRecord) -> Option<Lobes> {
+        let (u, v, point) = (hit_record.u, hit_record.v, &hit_record.point);
+        let scalar =
+            |texture: &BoxedTexture| texture.value(u, v, point).luminance().clamp(0.0, 1.0);
+
+        let basis = Onb::from_w_and_tangent(hit_record.normal, hit_record.tangent);
+        let wo = basis.project(-ray.direction().normalized());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        // Disney's mapping from specular to the reflectance at normal incidence
+        let f0 = 0.08 * scalar(&self.specular);
+        let ior = (1.0 + f0.sqrt()) / (1.0 - f0.sqrt());
+        let eta = if hit_record.front_face {
+            ior
+        } else {
+            1.0 / ior
+        };
+
+        let roughness = scalar(&self.roughness).max(MIN_ROUGHNESS);
+        let clearcoat = scalar(&self.clearcoat);
+        let metallic = scalar(&self.metallic);
+        let transmission = scalar(&self.transmission);
+
+        // Fraction of light passed on by each layer
+        let coat_weight = clearcoat * fresnel::dielectric(wo.z(), CLEARCOAT_IOR);
+        let below_coat = 1.0 - coat_weight;
+        let specular_weight = fresnel::dielectric(wo.z(), eta);
+        let below_specular = below_coat * (1.0 - metallic) * (1.0 - specular_weight);
+
+        Some(Lobes {
+            basis,
+            wo,
+            base_color: self.base_color.at_hit(hit_record),
+            eta,
+            distribution: TrowbridgeReitz::new(roughness, roughness),
+            coat_distribution: TrowbridgeReitz::new(
+                self.clearcoat_roughness.max(MIN_ROUGHNESS),
+                self.clearcoat_roughness.max(MIN_ROUGHNESS),
+            ),
+            clearcoat,
+            dielectric: below_coat * (1.0 - metallic),
+            transmission,
+            sheen: scalar(&self.sheen),
+            subsurface: scalar(&self.subsurface),
+            weights: [
+                coat_weight,
+                below_coat * metallic,
+                below_coat * (1.0 - metallic) * specular_weight,
+                below_specular * transmission,
+                below_specular * (1.0 - transmission),
+            ],
+        })
+    }
+}
+
+/// Index of each lobe in `Lobes::weights`.
+const COAT: usize = 0;
+const METAL: usize = 1;
+const SPECULAR: usize = 2;
+const TRANSMISSION: usize = 3;
+const DIFFUSE: usize = 4;
+
+/// Parameters of the material at a hit, in the local frame of the surface.
+struct Lobes {
+    basis: Onb,
+    wo: Vec3,
+    base_color: Color,
+    /// Index of refraction of the far side over that of the side the ray comes from.
+    eta: f32,
+    distribution: TrowbridgeReitz,
+    coat_distribution: TrowbridgeReitz,
+    clearcoat: f32,
+    /// Weight of the dielectric base below the clearcoat, before its specular reflection.
+    dielectric: f32,
+    transmission: f32,
+    sheen: f32,
+    subsurface: f32,
+    /// Share of the light that each lobe handles, seen from the outgoing direction.
+    weights: [f32; 5],
+}
+
+impl Lobes {
+    fn sample(&self) -> Option<Vec3> {
+        let total: f32 = self.weights.iter().sum();
+        let mut choice = rand::random::<f32>() * total;
+        let lobe = (0..self.weights.len())
+            .find(|&i| {
+                choice -= self.weights[i];
+                choice < 0.0
+            })
+            .unwrap_or(DIFFUSE);
+
+        let wo = self.wo;
+        match lobe {
+            COAT => Some((-wo).reflect(self.coat_distribution.sample_visible_normal(wo))),
+            METAL | SPECULAR => Some((-wo).reflect(self.distribution.sample_visible_normal(wo))),
+            TRANSMISSION => {
+                let wm = self.distribution.sample_visible_normal(wo);
+                fresnel::refract(wo, wm, self.eta)
+            }
+            _ => {
+                let (r1, r2): (f32, f32) = rand::random();
+                let r = r1.sqrt();
+                let phi = 2.0 * PI * r2;
+                let z = (1.0 - r1).sqrt();
+                // Translucency sends part of the diffuse light through the surface
+                let z = if rand::random::<f32>() < self.subsurface {
+                    -z
+                } else {
+                    z
+                };
+                Some(Vec3::new(r * phi.cos(), r * phi.sin(), z))
+            }
+        }
+    }
+
+    /// BSDF times the absolute cosine of `wi`.
+    fn eval(&self, wi: Vec3) -> Color {
+        let wo = self.wo;
+        let mut f = Color::new(0.0, 0.0, 0.0);
+
+        if wi.z() > 0.0 {
+            let wm = (wo + wi).normalized();
+            let cos_o_m = wo.dot(wm);
+
+            // The cosine of the incoming direction cancels against the microfacet denominator
+            let coat =
+                self.coat_distribution.d(wm) * self.coat_distribution.g(wo, wi) / (4.0 * wo.z());
+            f += Color::new(1.0, 1.0, 1.0)
+                * (self.clearcoat * fresnel::dielectric(cos_o_m, CLEARCOAT_IOR) * coat);
+
+            let specular = self.distribution.d(wm) * self.distribution.g(wo, wi) / (4.0 * wo.z());
+            let schlick = self.base_color
+                + (Color::new(1.0, 1.0, 1.0) + -1.0 * self.base_color)
+                    * (1.0 - cos_o_m.clamp(0.0, 1.0)).powi(5);
+            f += schlick * (self.weights[METAL] * specular);
+            f += Color::new(1.0, 1.0, 1.0)
+                * (self.dielectric * fresnel::dielectric(cos_o_m, self.eta) * specular);
+
+            let sheen = self.sheen * (1.0 - wi.dot(wm).clamp(0.0, 1.0)).powi(5);
+            let diffuse = (1.0 - sheen) * self.base_color + sheen * Color::new(1.0, 1.0, 1.0);
+            f += diffuse * (self.weights[DIFFUSE] * (1.0 - self.subsurface) * wi.z() / PI);
+        } else if wi.z() < 0.0 {
+            f += self.base_color * (self.weights[DIFFUSE] * self.subsurface * -wi.z() / PI);
+
+            if let Some((wm, dwm_dwi)) = self.transmission_half_vector(wi) {
+                let transmitted = (1.0 - fresnel::dielectric(wo.dot(wm), self.eta))
+                    * self.distribution.d(wm)
+                    * self.distribution.g(wo, wi)
+                    * wo.dot(wm)
+                    * dwm_dwi
+                    / wo.z();
+                f += self.base_color * (self.dielectric * self.transmission * transmitted);
+            }
+        }
+
+        f
+    }
+
+    fn pdf(&self, wi: Vec3) -> f32 {
+        let wo = self.wo;
+        let total: f32 = self.weights.iter().sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        let p = self.weights.map(|w| w / total);
+
+        if wi.z() > 0.0 {
+            let wm = (wo + wi).normalized();
+            let reflection = |distribution: &TrowbridgeReitz| {
+                distribution.visible_normal_pdf(wo, wm) / (4.0 * wo.dot(wm).abs())
+            };
+
+            p[COAT] * reflection(&self.coat_distribution)
+                + (p[METAL] + p[SPECULAR]) * reflection(&self.distribution)
+                + p[DIFFUSE] * (1.0 - self.subsurface) * wi.z() / PI
+        } else if wi.z() < 0.0 {
+            let transmission = self
+                .transmission_half_vector(wi)
+                .map_or(0.0, |(wm, dwm_dwi)| {
+                    self.distribution.visible_normal_pdf(wo, wm) * dwm_dwi
+                });
+
+            p[TRANSMISSION] * transmission + p[DIFFUSE] * self.subsurface * -wi.z() / PI
+        } else {
+            0.0
+        }
+    }
+
+    /// Finds the microfacet normal that refracts `wo` into `wi`, together with the
+    /// Jacobian of the change of variables from the normal to the refracted direction.
+    fn transmission_half_vector(&self, wi: Vec3) -> Option<(Vec3, f32)> {
+        let wo = self.wo;
+        let wm = wo + wi * self.eta;
+        if wm.near_zero() {
+            return None;
+        }
+        let wm = wm.normalized();
+        let wm = if wm.z() < 0.0 { -wm } else { wm };
+
+        // Discard back-facing microfacets
+        if wo.dot(wm) <= 0.0 || wi.dot(wm) >= 0.0 {
+            return None;
+        }
+
+        let denominator = wi.dot(wm) + wo.dot(wm) / self.eta;
+        Some((wm, wi.dot(wm).abs() / (denominator * denominator)))
+    }
+}
+
+impl Material for Principled<'_> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
+        let lobes = self.lobes(ray, hit_record)?;
+        let wi = lobes.sample()?.normalized();
+
+        // Weighting by all lobes rather than just the sampled one keeps the estimate
+        // consistent however the lobes overlap.
+        let pdf = lobes.pdf(wi);
+        if pdf <= 0.0 {
+            return None;
+        }
+
+        Some(ScatterRecord {
+            attenuation: lobes.eval(wi) / pdf,
+            scattered: Ray::new_time_based(hit_record.point, lobes.basis.local(wi), ray.time()),
+            pdf,
+        })
+    }
+
+    fn eval(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        self.lobes(ray, hit_record)
+            .map_or(Color::new(0.0, 0.0, 0.0), |lobes| {
+                lobes.eval(lobes.basis.project(direction.normalized()))
+            })
+    }
+
+    fn pdf(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        self.lobes(ray, hit_record).map_or(0.0, |lobes| {
+            lobes.pdf(lobes.basis.project(direction.normalized()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::materials::testing::furnace;
+
+    use super::*;
+
+    /// White variants of each kind of surface the material covers.
+    fn white_materials(roughness: f32) -> [(&'static str, Principled<'static>); 4] {
+        let white = || Principled::new(Color::new(1.0, 1.0, 1.0)).with_roughness(roughness);
+        [
+            ("dielectric", white()),
+            ("metal", white().with_metallic(1.0)),
+            ("glass", white().with_transmission(1.0)),
+            ("clearcoat", white().with_clearcoat(1.0, 0.1)),
+        ]
+    }
+
+    #[test]
+    fn white_surfaces_never_reflect_more_light_than_they_receive() {
+        for roughness in [0.05, 0.5, 1.0] {
+            for (name, material) in white_materials(roughness) {
+                let albedo = furnace(&material).r();
+                assert!(albedo <= 1.01, "{name} at {roughness} reflects {albedo}");
+            }
+        }
+    }
+
+    /// Light is only lost to shadowing between microfacets, which is small for smooth
+    /// surfaces.
+    #[test]
+    fn smooth_white_surfaces_reflect_almost_all_light() {
+        for (name, material) in white_materials(0.2) {
+            let albedo = furnace(&material).r();
+            assert!(albedo >= 0.95, "{name} reflects {albedo}");
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{color::Color, medium::Medium, objects::HitRecord, ray::Ray, vec3::Vec3};
+
+use super::{Dielectric, Material, ScatterRecord};
+
+/// Translucent material such as skin, marble or wax, where light enters the object and
+/// bounces around below the surface before it leaves again, possibly somewhere else.
+///
+/// The object is bounded by a dielectric surface with an index of refraction of 1.4 and
+/// filled with a scattering medium, in which the tracer follows a random walk. The object
+/// must be closed.
+///
+/// Light sampling only sees the reflection off a rough surface, while light reaching the
+/// inside is left to the random walk. Point, spot and directional lights, which the walk can't
+/// find, therefore don't shine into the object, and with a smooth surface they leave it
+/// completely dark, like `Metal`.
+#[derive(Clone)]
+pub struct Subsurface<'a> {
+    surface: Dielectric<'a>,
+    medium: Medium,
+}
+
+impl<'a> Subsurface<'a> {
+    /// Creates a subsurface material that looks roughly like `albedo` once light has bounced
+    /// around inside of it, where light travels `mean_free_path` on average between two
+    /// bounces. Light reflected back inside by the surface makes it a bit darker.
+    /// A longer mean free path in a color channel lets that color shine through further.
+    pub fn new(albedo: Color, mean_free_path: Color) -> Self {
+        Self {
+            surface: Dielectric::new(1.4),
+            medium: Medium::scattering(mean_free_path, albedo.map(single_scattering_albedo)),
+        }
+    }
+
+    /// Roughens the surface with GGX microfacets in `0..=1`.
+    pub fn with_roughness(self, roughness: f32) -> Self {
+        Self {
+            surface: self.surface.with_roughness(roughness),
+            ..self
+        }
+    }
+}
+
+/// Finds the albedo of a single scattering event that makes a thick slab reflect `albedo`
+/// after any number of bounces, which makes the color of the material predictable.
+/// Reference: Chiang et al., "Practical and Controllable Subsurface Scattering for
+/// Production Path Tracing" (2016).
+fn single_scattering_albedo(albedo: f32) -> f32 {
+    let albedo = albedo.clamp(0.0, 1.0);
+    let s = 4.097_12 + 4.208_63 * albedo
+        - (9.592_17 + 41.680_8 * albedo + 17.712_6 * albedo * albedo).sqrt();
+    1.0 - s * s
+}
+
+impl Material for Subsurface<'_> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<ScatterRecord> {
+        self.surface.scatter(ray, hit_record)
+    }
+
+    fn eval(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> Color {
+        self.surface.eval(ray, hit_record, direction)
+    }
+
+    fn pdf(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3) -> f32 {
+        self.surface.pdf(ray, hit_record, direction)
+    }
+
+    fn medium(&self) -> Option<Medium> {
+        Some(self.medium)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{color::Color, objects::HitRecord, ray::Ray, vec3::Vec3};
+
+use super::Material;
+
+/// Average throughput of rays scattering off the material under the same light from all
+/// directions, which is how much of that light the material reflects.
+pub fn furnace(material: &dyn Material) -> Color {
+    const SAMPLES: usize = 100_000;
+    let hit_record = hit_from_above(material);
+
+    let mut sum = Color::new(0.0, 0.0, 0.0);
+    for _ in 0..SAMPLES {
+        // Arriving from anywhere above the surface
+        let direction = Vec3::random_on_unit_sphere();
+        let direction = if direction.z() > 0.0 {
+            -direction
+        } else {
+            direction
+        };
+        let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0) - direction, direction);
+        if let Some(scatter) = material.scatter(&ray, &hit_record) {
+            sum += scatter.attenuation;
+        }
+    }
+    sum / SAMPLES as f32
+}
+
+/// Hit on the xy plane at the origin, with the normal pointing up the z axis.
+pub fn hit_from_above(material: &dyn Material) -> HitRecord<'_> {
+    HitRecord {
+        point: Vec3::zero(),
+        normal: Vec3::new(0.0, 0.0, 1.0),
+        t: 1.0,
+        front_face: true,
+        material,
+        u: 0.0,
+        v: 0.0,
+        tangent: Vec3::new(1.0, 0.0, 0.0),
+        bitangent: Vec3::new(0.0, 1.0, 0.0),
+        footprint: 0.0,
+    }
+}
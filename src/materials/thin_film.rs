@@ -0,0 +1,166 @@
+extern crate overload;
+use overload::overload;
+use std::f32::consts::PI;
+use std::ops;
+
+use crate::{
+    color::Color,
+    objects::HitRecord,
+    ray::Ray,
+    spectrum,
+    textures::{BoxedTexture, Texture},
+};
+
+/// Thin transparent film on top of a surface, such as soap, oil or the anti-reflective
+/// coating of a lens. Light reflected at the top and the bottom of the film interferes,
+/// which makes the reflectance depend on the wavelength and shows up as iridescent colors.
+#[derive(Clone)]
+pub struct ThinFilm<'a> {
+    thickness: BoxedTexture<'a>,
+    min_thickness: f32,
+    max_thickness: f32,
+    ior: f32,
+}
+
+impl<'a> ThinFilm<'a> {
+    /// Creates a film of uniform thickness in nanometers with the given index of refraction.
+    pub fn new(thickness: f32, ior: f32) -> Self {
+        Self {
+            thickness: Box::new(0.0),
+            min_thickness: thickness,
+            max_thickness: thickness,
+            ior,
+        }
+    }
+
+    /// Creates a film whose thickness varies between `min_thickness` and `max_thickness`
+    /// nanometers with the luminance of the texture, like the swirls of a soap bubble.
+    pub fn from_texture(
+        texture: impl Texture + Send + Sync + 'a,
+        min_thickness: f32,
+        max_thickness: f32,
+        ior: f32,
+    ) -> Self {
+        Self {
+            thickness: Box::new(texture),
+            min_thickness,
+            max_thickness,
+            ior,
+        }
+    }
+
+    /// Reflectance of the film for light arriving with the cosine `cos_theta_i` from a medium
+    /// with the index of refraction `outside`. The substrate below the film has the complex
+    /// index of refraction `eta + i k`, returned by `substrate` for a wavelength in nanometers.
+    ///
+    /// A spectral ray gets the reflectance at its wavelength as a gray, so materials with a
+    /// film are dispersive. Otherwise the reflectance is integrated to RGB.
+    /// Reference: Born and Wolf, "Principles of Optics", section 1.6.
+    pub(super) fn reflectance(
+        &self,
+        ray: &Ray,
+        hit_record: &HitRecord,
+        cos_theta_i: f32,
+        outside: f32,
+        substrate: impl Fn(f32) -> (f32, f32),
+    ) -> Color {
+        let (u, v, point) = (hit_record.u, hit_record.v, &hit_record.point);
+        let t = self
+            .thickness
+            .value(u, v, point)
+            .luminance()
+            .clamp(0.0, 1.0);
+        let thickness = self.min_thickness + t * (self.max_thickness - self.min_thickness);
+
+        let (n1, n2) = (outside, self.ior);
+        let cos1 = cos_theta_i.clamp(0.0, 1.0);
+        let sin2_film = (n1 / n2).powi(2) * (1.0 - cos1 * cos1);
+        if sin2_film >= 1.0 {
+            // Total internal reflection at the top of the film
+            return Color::new(1.0, 1.0, 1.0);
+        }
+        let cos2 = (1.0 - sin2_film).sqrt();
+
+        // Amplitudes reflected at the top of the film, for s and p polarized light
+        let r12_s = Complex::real((n1 * cos1 - n2 * cos2) / (n1 * cos1 + n2 * cos2));
+        let r12_p = Complex::real((n2 * cos1 - n1 * cos2) / (n2 * cos1 + n1 * cos2));
+
+        let reflectance = |lambda: f32| {
+            let (eta, k) = substrate(lambda);
+            let n3 = Complex::new(eta, k);
+            let n3_squared = n3 * n3;
+            // n3 cos θ3 follows from Snell's law without computing the complex angle
+            let n3_cos3 = (n3_squared - Complex::real(n2 * n2 * sin2_film)).sqrt();
+
+            // Amplitudes reflected at the bottom of the film
+            let n2_cos2 = Complex::real(n2 * cos2);
+            let r23_s = (n2_cos2 - n3_cos3) / (n2_cos2 + n3_cos3);
+            let n3_squared_cos2 = n3_squared * Complex::real(cos2);
+            let n2_n3_cos3 = Complex::real(n2) * n3_cos3;
+            let r23_p = (n3_squared_cos2 - n2_n3_cos3) / (n3_squared_cos2 + n2_n3_cos3);
+
+            // Sum over all the reflections inside the film, each one shifted by the phase of
+            // a round trip
+            let phase = 4.0 * PI * n2 * thickness * cos2 / lambda;
+            let shift = Complex::new(phase.cos(), phase.sin());
+            let airy = |r12: Complex, r23: Complex| {
+                ((r12 + r23 * shift) / (Complex::real(1.0) + r12 * r23 * shift)).norm_squared()
+            };
+
+            (0.5 * (airy(r12_s, r23_s) + airy(r12_p, r23_p))).clamp(0.0, 1.0)
+        };
+
+        match ray.wavelength() {
+            Some(lambda) => {
+                let value = reflectance(lambda);
+                Color::new(value, value, value)
+            }
+            None => spectrum::reflectance_to_rgb(reflectance),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Complex {
+    re: f32,
+    im: f32,
+}
+
+impl Complex {
+    fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    fn real(re: f32) -> Self {
+        Self::new(re, 0.0)
+    }
+
+    fn norm_squared(self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Principal square root, with a non-negative real part.
+    fn sqrt(self) -> Self {
+        let norm = self.norm_squared().sqrt();
+        let re = (0.5 * (norm + self.re)).max(0.0).sqrt();
+        let im = (0.5 * (norm - self.re)).max(0.0).sqrt();
+        Self::new(re, im.copysign(self.im))
+    }
+}
+
+overload!((a: Complex) + (b: Complex) -> Complex {
+    Complex::new(a.re + b.re, a.im + b.im)
+});
+overload!((a: Complex) - (b: Complex) -> Complex {
+    Complex::new(a.re - b.re, a.im - b.im)
+});
+overload!((a: Complex) * (b: Complex) -> Complex {
+    Complex::new(a.re * b.re - a.im * b.im, a.re * b.im + a.im * b.re)
+});
+overload!((a: Complex) / (b: Complex) -> Complex {
+    let denominator = b.norm_squared();
+    Complex::new(
+        (a.re * b.re + a.im * b.im) / denominator,
+        (a.im * b.re - a.re * b.im) / denominator,
+    )
+});
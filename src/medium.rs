@@ -0,0 +1,88 @@
+use crate::color::Color;
+
+/// Homogeneous medium filling the inside of a closed object.
+#[derive(Debug, Clone, Copy)]
+pub struct Medium {
+    /// Fraction of light absorbed per unit of distance, for each color channel.
+    absorption: Color,
+    /// Fraction of light scattered into another direction per unit of distance.
+    scattering: Color,
+}
+
+/// What happens to a ray travelling through a medium up to the next surface.
+pub enum Interaction {
+    /// The ray scatters after `distance`, its throughput is multiplied by `weight`.
+    Scatter { distance: f32, weight: Color },
+    /// The ray makes it to the surface, its throughput is multiplied by `weight`.
+    Pass { weight: Color },
+}
+
+impl Medium {
+    /// Creates a medium that turns white light into `color` after travelling `distance`.
+    pub fn absorbing(color: Color, distance: f32) -> Self {
+        Self {
+            absorption: color.map(|c| -c.max(1e-6).ln() / distance),
+            scattering: Color::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Creates a medium where light travels `mean_free_path` on average before it interacts
+    /// with a particle, and is scattered rather than absorbed with the probability `albedo`.
+    pub fn scattering(mean_free_path: Color, albedo: Color) -> Self {
+        let extinction = mean_free_path.map(|d| 1.0 / d.max(1e-6));
+        Self {
+            absorption: extinction * albedo.map(|a| 1.0 - a),
+            scattering: extinction * albedo,
+        }
+    }
+
+    /// Fraction of light that makes it through `distance` of the medium (Beer-Lambert law).
+    pub fn transmittance(&self, distance: f32) -> Color {
+        (self.absorption + self.scattering).map(|a| (-a * distance).exp())
+    }
+
+    /// Samples where a ray scatters on its way through `distance` of the medium.
+    ///
+    /// The distance is sampled for a randomly chosen color channel, the same way
+    /// `ConstantMedium` does, and the weights account for all channels at once.
+    /// Media that only absorb light never scatter.
+    /// Reference: Chiang et al., "Practical and Controllable Subsurface Scattering for
+    /// Production Path Tracing" (2016).
+    pub fn sample_interaction(&self, distance: f32) -> Interaction {
+        if self.scattering == Color::new(0.0, 0.0, 0.0) {
+            return Interaction::Pass {
+                weight: self.transmittance(distance),
+            };
+        }
+
+        let extinction = self.absorption + self.scattering;
+        let channels = [extinction.r(), extinction.g(), extinction.b()];
+        let channel = ((rand::random::<f32>() * 3.0) as usize).min(2);
+        let sampled_distance = sample_free_flight(channels[channel]);
+
+        let scattered = sampled_distance < distance;
+        let distance = sampled_distance.min(distance);
+        let transmittance = self.transmittance(distance);
+        let transmittances = [transmittance.r(), transmittance.g(), transmittance.b()];
+        if scattered {
+            // Density of scattering at the distance, averaged over the channels
+            let pdf = (0..3).map(|i| channels[i] * transmittances[i]).sum::<f32>() / 3.0;
+            Interaction::Scatter {
+                distance,
+                weight: self.scattering * transmittance / pdf,
+            }
+        } else {
+            // Probability of getting through, averaged over the channels
+            let pdf = transmittances.iter().sum::<f32>() / 3.0;
+            Interaction::Pass {
+                weight: transmittance / pdf,
+            }
+        }
+    }
+}
+
+/// Samples how far light travels through a medium with the given extinction coefficient
+/// before it hits a particle, which is exponentially distributed.
+pub fn sample_free_flight(extinction: f32) -> f32 {
+    -(1.0 - rand::random::<f32>()).ln() / extinction
+}
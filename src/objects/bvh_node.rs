@@ -70,7 +70,7 @@ impl<'a> BvhNode<'a> {
 }
 
 impl Object for BvhNode<'_> {
-    fn hit(&self, ray: &crate::ray::Ray, t_min: f32, t_max: f32) -> Option<super::HitRecord> {
+    fn hit(&self, ray: &crate::ray::Ray, t_min: f32, t_max: f32) -> Option<super::HitRecord<'_>> {
         if !self.bounding_box.hit(ray, t_min, t_max) {
             return None;
         }
@@ -1,6 +1,7 @@
 use crate::{
     color::Color,
     materials::{BoxedMaterial, Isotropic, Material},
+    medium,
     ray::Ray,
     textures::Texture,
     time::Time,
@@ -13,7 +14,7 @@ use super::{BoxedObject, HitRecord, Object};
 pub struct ConstantMedium<'a> {
     boundary: BoxedObject<'a>,
     phase_function: BoxedMaterial<'a>,
-    neg_inv_density: f32,
+    density: f32,
 }
 
 impl<'a> ConstantMedium<'a> {
@@ -33,7 +34,7 @@ impl<'a> ConstantMedium<'a> {
         Self {
             boundary,
             phase_function,
-            neg_inv_density: -1.0 / density,
+            density,
         }
     }
 
@@ -56,13 +57,9 @@ impl<'a> ConstantMedium<'a> {
 }
 
 impl Object for ConstantMedium<'_> {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<super::HitRecord> {
-        let mut rec1 = self
-            .boundary
-            .hit(ray, -std::f32::INFINITY, std::f32::INFINITY)?;
-        let mut rec2 = self
-            .boundary
-            .hit(ray, rec1.t + 0.0001, std::f32::INFINITY)?;
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<super::HitRecord<'_>> {
+        let mut rec1 = self.boundary.hit(ray, -f32::INFINITY, f32::INFINITY)?;
+        let mut rec2 = self.boundary.hit(ray, rec1.t + 0.0001, f32::INFINITY)?;
 
         if rec1.t < t_min {
             rec1.t = t_min;
@@ -79,7 +76,7 @@ impl Object for ConstantMedium<'_> {
 
         let ray_length = ray.direction().length();
         let distance_inside_boundary = (rec2.t - rec1.t) * ray_length;
-        let hit_distance = self.neg_inv_density * rand::random::<f32>().ln();
+        let hit_distance = medium::sample_free_flight(self.density);
 
         if hit_distance > distance_inside_boundary {
             return None;
@@ -94,6 +91,9 @@ impl Object for ConstantMedium<'_> {
             material: &*self.phase_function,
             u: 0.0,
             v: 0.0,
+            tangent: Vec3::zero(),
+            bitangent: Vec3::zero(),
+            footprint: 0.0,
             t,
             front_face: true,
         })
@@ -0,0 +1,164 @@
+use crate::{aabb::AABB, ray::Ray, time::Time};
+
+use super::{BoxedObject, HitRecord, Object};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    /// Everything inside the left object that is not inside the right one.
+    Difference,
+}
+
+impl CsgOperation {
+    fn contains(self, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => in_left || in_right,
+            CsgOperation::Intersection => in_left && in_right,
+            CsgOperation::Difference => in_left && !in_right,
+        }
+    }
+}
+
+/// Constructive solid geometry node combining two closed objects.
+#[derive(Clone)]
+pub struct Csg<'a> {
+    left: BoxedObject<'a>,
+    right: BoxedObject<'a>,
+    operation: CsgOperation,
+}
+
+impl<'a> Csg<'a> {
+    pub fn new(
+        left: impl Object + 'a + Send + Sync,
+        right: impl Object + 'a + Send + Sync,
+        operation: CsgOperation,
+    ) -> Self {
+        Self::new_boxed(Box::new(left), Box::new(right), operation)
+    }
+
+    pub fn new_boxed(
+        left: BoxedObject<'a>,
+        right: BoxedObject<'a>,
+        operation: CsgOperation,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            operation,
+        }
+    }
+
+    pub fn union(
+        left: impl Object + 'a + Send + Sync,
+        right: impl Object + 'a + Send + Sync,
+    ) -> Self {
+        Self::new(left, right, CsgOperation::Union)
+    }
+
+    pub fn intersection(
+        left: impl Object + 'a + Send + Sync,
+        right: impl Object + 'a + Send + Sync,
+    ) -> Self {
+        Self::new(left, right, CsgOperation::Intersection)
+    }
+
+    pub fn difference(
+        left: impl Object + 'a + Send + Sync,
+        right: impl Object + 'a + Send + Sync,
+    ) -> Self {
+        Self::new(left, right, CsgOperation::Difference)
+    }
+}
+
+impl<'a> Csg<'a> {
+    /// Boundaries of the combined object crossed by the ray within `t_min..t_max`, in order.
+    /// Hits are merged lazily, so taking only the first one skips the rest of the work.
+    fn boundaries(
+        &self,
+        ray: &Ray,
+        t_min: f32,
+        t_max: f32,
+    ) -> impl Iterator<Item = HitRecord<'_>> + '_ {
+        // Rays missing the bounds can't cross the result, wherever they start
+        let instant = Time::new(ray.time(), ray.time());
+        let missed = self
+            .bounding_box(instant)
+            .is_some_and(|bounds| !bounds.hit(ray, t_min, t_max));
+        let (left_hits, right_hits) = if missed {
+            (Vec::new(), Vec::new())
+        } else {
+            // The children are queried along the whole line, so we know whether the ray
+            // starts inside of them.
+            (
+                self.left.hit_all(ray, f32::NEG_INFINITY, f32::INFINITY),
+                self.right.hit_all(ray, f32::NEG_INFINITY, f32::INFINITY),
+            )
+        };
+
+        let mut in_left = left_hits.first().is_some_and(|rec| !rec.front_face);
+        let mut in_right = right_hits.first().is_some_and(|rec| !rec.front_face);
+
+        let mut left_hits = left_hits.into_iter().peekable();
+        let mut right_hits = right_hits.into_iter().peekable();
+
+        std::iter::from_fn(move || loop {
+            let from_left = match (left_hits.peek(), right_hits.peek()) {
+                (Some(left), Some(right)) => left.t <= right.t,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => return None,
+            };
+
+            let was_inside = self.operation.contains(in_left, in_right);
+            let mut rec = if from_left {
+                let rec = left_hits.next().unwrap();
+                in_left = rec.front_face;
+                rec
+            } else {
+                let rec = right_hits.next().unwrap();
+                in_right = rec.front_face;
+                rec
+            };
+
+            // The hits are sorted, so nothing further along can be in range
+            if rec.t >= t_max {
+                return None;
+            }
+            if was_inside == self.operation.contains(in_left, in_right) || rec.t < t_min {
+                continue;
+            }
+
+            // The surface of a subtracted object faces into the result, so entering it means
+            // leaving the result. The normal is already oriented towards the ray and stays as is.
+            if !from_left && self.operation == CsgOperation::Difference {
+                rec.front_face = !rec.front_face;
+            }
+
+            return Some(rec);
+        })
+    }
+}
+
+impl Object for Csg<'_> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>> {
+        self.boundaries(ray, t_min, t_max).next()
+    }
+
+    fn hit_all(&self, ray: &Ray, t_min: f32, t_max: f32) -> Vec<HitRecord<'_>> {
+        self.boundaries(ray, t_min, t_max).collect()
+    }
+
+    fn bounding_box(&self, timeframe: Time) -> Option<AABB> {
+        let left = self.left.bounding_box(timeframe)?;
+
+        match self.operation {
+            CsgOperation::Union => Some(AABB::surrounding_box(
+                &left,
+                &self.right.bounding_box(timeframe)?,
+            )),
+            // The result never extends past the left object.
+            CsgOperation::Intersection | CsgOperation::Difference => Some(left),
+        }
+    }
+}
@@ -82,7 +82,7 @@ impl<'a> Cuboid<'a> {
 }
 
 impl Object for Cuboid<'_> {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>> {
         let mut closest_so_far = t_max;
         let mut hit_record = None;
 
@@ -0,0 +1,311 @@
+use crate::{
+    aabb::AABB,
+    materials::{BoxedMaterial, Material},
+    ray::Ray,
+    time::Time,
+    vec3::{Point3, Vec3},
+};
+
+use super::{HitRecord, Object};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveType {
+    /// Flat strip that always faces the incoming ray.
+    Ribbon,
+    /// Strip that is shaded as if it was a round tube.
+    Cylinder,
+}
+
+/// Cubic Bezier curve with a width that varies linearly along its length.
+///
+/// The curve is intersected by recursively subdividing it in a coordinate system
+/// aligned with the ray, until the segments are flat enough to be treated as lines.
+/// Reference: Nakamaru and Ohno, "Ray tracing for curves primitive" (2002).
+#[derive(Clone)]
+pub struct Curve<'a> {
+    control_points: [Point3; 4],
+    widths: (f32, f32),
+    u_range: (f32, f32),
+    curve_type: CurveType,
+    material: BoxedMaterial<'a>,
+}
+
+impl<'a> Curve<'a> {
+    pub fn new(
+        control_points: [Point3; 4],
+        widths: (f32, f32),
+        curve_type: CurveType,
+        material: impl Material + Send + Sync + 'a,
+    ) -> Self {
+        Self::new_boxed(control_points, widths, curve_type, Box::new(material))
+    }
+
+    pub fn new_boxed(
+        control_points: [Point3; 4],
+        widths: (f32, f32),
+        curve_type: CurveType,
+        material: BoxedMaterial<'a>,
+    ) -> Self {
+        Self {
+            control_points,
+            widths,
+            u_range: (0.0, 1.0),
+            curve_type,
+            material,
+        }
+    }
+
+    /// Splits the curve into `count` segments with the same shape.
+    /// Every segment has a tighter bounding box, which lets a BVH cull long curves much better.
+    pub fn split(&self, count: usize) -> Vec<Self> {
+        (0..count)
+            .map(|i| {
+                let u0 = i as f32 / count as f32;
+                let u1 = (i + 1) as f32 / count as f32;
+                let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+
+                Self {
+                    control_points: subsegment(&self.control_points, u0, u1),
+                    widths: (
+                        lerp(self.widths.0, self.widths.1, u0),
+                        lerp(self.widths.0, self.widths.1, u1),
+                    ),
+                    u_range: (
+                        lerp(self.u_range.0, self.u_range.1, u0),
+                        lerp(self.u_range.0, self.u_range.1, u1),
+                    ),
+                    curve_type: self.curve_type,
+                    material: self.material.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn width_at(&self, u: f32) -> f32 {
+        self.widths.0 + (self.widths.1 - self.widths.0) * u
+    }
+
+    /// Finds the closest hit of the ray space control points with the ray, which runs along
+    /// the z-axis. Returns the distance along the ray and the curve parameter of the hit.
+    fn recursive_intersect(
+        &self,
+        cp: &[Vec3; 4],
+        u_range: (f32, f32),
+        depth: u32,
+        z_range: (f32, f32),
+    ) -> Option<(f32, f32)> {
+        let half_width = 0.5 * self.width_at(u_range.0).max(self.width_at(u_range.1));
+        let (min, max) = bounds(cp);
+        if min.x() - half_width > 0.0
+            || max.x() + half_width < 0.0
+            || min.y() - half_width > 0.0
+            || max.y() + half_width < 0.0
+            || min.z() - half_width > z_range.1
+            || max.z() + half_width < z_range.0
+        {
+            return None;
+        }
+
+        if depth > 0 {
+            let (left, right) = split_in_half(cp);
+            let u_mid = 0.5 * (u_range.0 + u_range.1);
+
+            let hit_left = self.recursive_intersect(&left, (u_range.0, u_mid), depth - 1, z_range);
+            let z_max = hit_left.map_or(z_range.1, |(z, _)| z);
+            let hit_right =
+                self.recursive_intersect(&right, (u_mid, u_range.1), depth - 1, (z_range.0, z_max));
+
+            return hit_right.or(hit_left);
+        }
+
+        // Reject hits beyond the start and end of the segment, the neighbouring segments
+        // cover those.
+        let edge = (cp[1].y() - cp[0].y()) * -cp[0].y() + cp[0].x() * (cp[0].x() - cp[1].x());
+        if edge < 0.0 {
+            return None;
+        }
+        let edge = (cp[2].y() - cp[3].y()) * -cp[3].y() + cp[3].x() * (cp[3].x() - cp[2].x());
+        if edge < 0.0 {
+            return None;
+        }
+
+        // Closest point of the segment's line to the ray
+        let segment = Vec3::new(cp[3].x() - cp[0].x(), cp[3].y() - cp[0].y(), 0.0);
+        let denominator = segment.length_squared();
+        if denominator == 0.0 {
+            return None;
+        }
+        let w =
+            ((-cp[0].x() * segment.x() - cp[0].y() * segment.y()) / denominator).clamp(0.0, 1.0);
+        let u = u_range.0 + (u_range.1 - u_range.0) * w;
+
+        let closest = evaluate(cp, w);
+        let width = self.width_at(u);
+        if closest.x() * closest.x() + closest.y() * closest.y() > 0.25 * width * width {
+            return None;
+        }
+        if !(z_range.0..z_range.1).contains(&closest.z()) {
+            return None;
+        }
+
+        Some((closest.z(), u))
+    }
+}
+
+impl Object for Curve<'_> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>> {
+        // Transform the control points into a coordinate system where the ray starts at the
+        // origin and runs along the z-axis.
+        let ray_length = ray.direction().length();
+        let dz = ray.direction() / ray_length;
+        let helper = if dz.x().abs() > 0.9 {
+            Vec3::new(0.0, 1.0, 0.0)
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let dx = dz.cross(helper).normalized();
+        let dy = dz.cross(dx);
+        let cp = self.control_points.map(|p| {
+            let p = p - ray.origin();
+            Vec3::new(p.dot(dx), p.dot(dy), p.dot(dz))
+        });
+
+        // Subdivide until the segments deviate from a line by less than a fraction of the width
+        let flatness = (0..2)
+            .map(|i| {
+                let d = cp[i] - 2.0 * cp[i + 1] + cp[i + 2];
+                d.x().abs().max(d.y().abs()).max(d.z().abs())
+            })
+            .fold(0.0, f32::max);
+        let epsilon = 0.05 * self.widths.0.max(self.widths.1);
+        let depth = (std::f32::consts::SQRT_2 * 6.0 * flatness / (8.0 * epsilon))
+            .log2()
+            .floor()
+            .clamp(0.0, 20.0) as u32
+            / 2;
+
+        let z_range = (t_min * ray_length, t_max * ray_length);
+        let (z, u) = self.recursive_intersect(&cp, (0.0, 1.0), depth.min(10), z_range)?;
+
+        let t = z / ray_length;
+        let point = ray.at(t);
+        let tangent = derivative(&self.control_points, u).normalized();
+
+        // The ribbon faces the ray, so its normal is the view direction without the part
+        // along the curve.
+        let view = -dz;
+        let facing = (view - tangent * view.dot(tangent)).normalized();
+
+        // Signed distance of the hit from the center line, in units of half the width
+        let side = tangent.cross(facing);
+        let offset = point - evaluate(&self.control_points, u);
+        let across = (offset.dot(side) / (0.5 * self.width_at(u))).clamp(-1.0, 1.0);
+
+        let u = self.u_range.0 + (self.u_range.1 - self.u_range.0) * u;
+        let v = 0.5 + 0.5 * across;
+        // Only the closest crossing is found, so a cut out part doesn't reveal the rest of
+        // the curve behind it
+        if !self.material.alpha_test(u, v, &point) {
+            return None;
+        }
+
+        let normal = match self.curve_type {
+            CurveType::Ribbon => facing,
+            // Bend the normal around the tube according to how far off center the hit is
+            CurveType::Cylinder => (1.0 - across * across).sqrt() * facing + across * side,
+        };
+
+        Some(HitRecord {
+            point,
+            normal,
+            t,
+            front_face: true,
+            material: self.material.as_ref(),
+            u,
+            v,
+            tangent,
+            bitangent: side,
+            footprint: 0.0,
+        })
+    }
+
+    fn bounding_box(&self, _timeframe: Time) -> Option<AABB> {
+        // The curve lies within the convex hull of its control points
+        let half_width = 0.5 * self.widths.0.max(self.widths.1);
+        let expand = Vec3::new(half_width, half_width, half_width);
+        let (min, max) = bounds(&self.control_points);
+
+        Some(AABB::new(min - expand, max + expand))
+    }
+}
+
+fn bounds(points: &[Vec3; 4]) -> (Vec3, Vec3) {
+    points
+        .iter()
+        .skip(1)
+        .fold((points[0], points[0]), |(min, max), p| {
+            (
+                Vec3::new(min.x().min(p.x()), min.y().min(p.y()), min.z().min(p.z())),
+                Vec3::new(max.x().max(p.x()), max.y().max(p.y()), max.z().max(p.z())),
+            )
+        })
+}
+
+fn lerp(a: Vec3, b: Vec3, t: f32) -> Vec3 {
+    (1.0 - t) * a + t * b
+}
+
+fn evaluate(cp: &[Vec3; 4], u: f32) -> Vec3 {
+    let a = [
+        lerp(cp[0], cp[1], u),
+        lerp(cp[1], cp[2], u),
+        lerp(cp[2], cp[3], u),
+    ];
+    let b = [lerp(a[0], a[1], u), lerp(a[1], a[2], u)];
+    lerp(b[0], b[1], u)
+}
+
+fn derivative(cp: &[Vec3; 4], u: f32) -> Vec3 {
+    let d = [cp[1] - cp[0], cp[2] - cp[1], cp[3] - cp[2]];
+    let derivative = 3.0 * lerp(lerp(d[0], d[1], u), lerp(d[1], d[2], u), u);
+
+    // The derivative vanishes at an end point with a doubled control point
+    if derivative.near_zero() {
+        cp[3] - cp[0]
+    } else {
+        derivative
+    }
+}
+
+/// Splits the curve at its midpoint using de Casteljau's algorithm.
+fn split_in_half(cp: &[Vec3; 4]) -> ([Vec3; 4], [Vec3; 4]) {
+    let a = [
+        lerp(cp[0], cp[1], 0.5),
+        lerp(cp[1], cp[2], 0.5),
+        lerp(cp[2], cp[3], 0.5),
+    ];
+    let b = [lerp(a[0], a[1], 0.5), lerp(a[1], a[2], 0.5)];
+    let mid = lerp(b[0], b[1], 0.5);
+
+    ([cp[0], a[0], b[0], mid], [mid, b[1], a[2], cp[3]])
+}
+
+/// Returns the control points of the part of the curve between `u0` and `u1`.
+fn subsegment(cp: &[Vec3; 4], u0: f32, u1: f32) -> [Vec3; 4] {
+    let blossom = |t0: f32, t1: f32, t2: f32| {
+        let a = [
+            lerp(cp[0], cp[1], t0),
+            lerp(cp[1], cp[2], t0),
+            lerp(cp[2], cp[3], t0),
+        ];
+        let b = [lerp(a[0], a[1], t1), lerp(a[1], a[2], t1)];
+        lerp(b[0], b[1], t2)
+    };
+
+    [
+        blossom(u0, u0, u0),
+        blossom(u0, u0, u1),
+        blossom(u0, u1, u1),
+        blossom(u1, u1, u1),
+    ]
+}
@@ -0,0 +1,311 @@
+use thiserror::Error;
+
+use crate::{
+    aabb::AABB,
+    materials::{BoxedMaterial, Material},
+    ray::Ray,
+    time::Time,
+    vec3::{Point3, Vec3},
+};
+
+use super::{triangle, HitRecord, Object};
+
+#[derive(Error, Debug)]
+pub enum HeightfieldError {
+    #[error("Failed to load the heightmap: {0}")]
+    Image(#[from] image::ImageError),
+    #[error("The heightfield needs at least 2x2 samples, got {0}x{1}")]
+    TooSmall(usize, usize),
+    #[error("Expected {expected} height samples, got {actual}")]
+    SampleCount { expected: usize, actual: usize },
+}
+
+/// Terrain defined by a regular grid of height samples.
+///
+/// The grid covers `size.x()` by `size.z()` starting at `corner`, and heights in `0..=1` are
+/// scaled by `size.y()`. Each grid cell is split into two triangles, which are found by walking
+/// the cells along the ray instead of putting every triangle into a BVH.
+#[derive(Clone)]
+pub struct Heightfield<'a> {
+    heights: Vec<f32>,
+    normals: Vec<Vec3>,
+    resolution_x: usize,
+    resolution_z: usize,
+    corner: Point3,
+    size: Vec3,
+    bounding_box: AABB,
+    material: BoxedMaterial<'a>,
+}
+
+impl<'a> Heightfield<'a> {
+    /// Creates a heightfield from `resolution_x * resolution_z` height samples in `0..=1`,
+    /// stored row by row along the x-axis.
+    pub fn new(
+        heights: Vec<f32>,
+        resolution_x: usize,
+        resolution_z: usize,
+        corner: Point3,
+        size: Vec3,
+        material: impl Material + Send + Sync + 'a,
+    ) -> Result<Self, HeightfieldError> {
+        if resolution_x < 2 || resolution_z < 2 {
+            return Err(HeightfieldError::TooSmall(resolution_x, resolution_z));
+        }
+        if heights.len() != resolution_x * resolution_z {
+            return Err(HeightfieldError::SampleCount {
+                expected: resolution_x * resolution_z,
+                actual: heights.len(),
+            });
+        }
+
+        let heights: Vec<f32> = heights.into_iter().map(|h| h * size.y()).collect();
+
+        let (min_height, max_height) = heights
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &h| {
+                (min.min(h), max.max(h))
+            });
+        // Make sure the bounding box is not degenerate for flat terrain
+        let bounding_box = AABB::new(
+            corner + Vec3::new(0.0, min_height - 0.0001, 0.0),
+            corner + Vec3::new(size.x(), max_height + 0.0001, size.z()),
+        );
+
+        let mut heightfield = Self {
+            heights,
+            normals: Vec::new(),
+            resolution_x,
+            resolution_z,
+            corner,
+            size,
+            bounding_box,
+            material: Box::new(material),
+        };
+        heightfield.normals = heightfield.vertex_normals();
+
+        Ok(heightfield)
+    }
+
+    /// Loads the heights from a grayscale image, where black is the lowest and white the
+    /// highest point. The image's x-axis runs along the world x-axis and its y-axis along
+    /// the world z-axis, so the same image used as an `ImageTexture` lines up with the terrain.
+    pub fn from_image(
+        filename: &str,
+        corner: Point3,
+        size: Vec3,
+        material: impl Material + Send + Sync + 'a,
+    ) -> Result<Self, HeightfieldError> {
+        let image = image::open(filename)?.to_luma16();
+        let heights = image
+            .pixels()
+            .map(|pixel| pixel[0] as f32 / u16::MAX as f32)
+            .collect();
+
+        Self::new(
+            heights,
+            image.width() as usize,
+            image.height() as usize,
+            corner,
+            size,
+            material,
+        )
+    }
+
+    fn cell_size(&self) -> (f32, f32) {
+        (
+            self.size.x() / (self.resolution_x - 1) as f32,
+            self.size.z() / (self.resolution_z - 1) as f32,
+        )
+    }
+
+    fn height(&self, i: usize, j: usize) -> f32 {
+        self.heights[j * self.resolution_x + i]
+    }
+
+    fn vertex(&self, i: usize, j: usize) -> Point3 {
+        let (dx, dz) = self.cell_size();
+        self.corner + Vec3::new(i as f32 * dx, self.height(i, j), j as f32 * dz)
+    }
+
+    fn vertex_uv(&self, i: usize, j: usize) -> (f32, f32) {
+        (
+            i as f32 / (self.resolution_x - 1) as f32,
+            1.0 - j as f32 / (self.resolution_z - 1) as f32,
+        )
+    }
+
+    /// Computes the vertex normals from the central differences of the heights.
+    fn vertex_normals(&self) -> Vec<Vec3> {
+        let (dx, dz) = self.cell_size();
+
+        (0..self.resolution_z)
+            .flat_map(|j| (0..self.resolution_x).map(move |i| (i, j)))
+            .map(|(i, j)| {
+                let (i0, i1) = (i.saturating_sub(1), (i + 1).min(self.resolution_x - 1));
+                let (j0, j1) = (j.saturating_sub(1), (j + 1).min(self.resolution_z - 1));
+
+                let slope_x = (self.height(i1, j) - self.height(i0, j)) / ((i1 - i0) as f32 * dx);
+                let slope_z = (self.height(i, j1) - self.height(i, j0)) / ((j1 - j0) as f32 * dz);
+
+                Vec3::new(-slope_x, 1.0, -slope_z).normalized()
+            })
+            .collect()
+    }
+
+    fn hit_cell(
+        &self,
+        ray: &Ray,
+        i: usize,
+        j: usize,
+        t_min: f32,
+        t_max: f32,
+    ) -> Option<HitRecord<'_>> {
+        let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+        let triangles = [
+            [corners[0], corners[2], corners[1]],
+            [corners[0], corners[3], corners[2]],
+        ];
+
+        let mut closest: Option<(f32, f32, f32, usize)> = None;
+        for (index, triangle) in triangles.iter().enumerate() {
+            let [v0, v1, v2] = triangle.map(|(i, j)| self.vertex(i, j));
+            let t_max = closest.map_or(t_max, |(t, ..)| t);
+            if let Some((t, b1, b2)) = triangle::intersect(ray, v0, v1, v2, t_min, t_max) {
+                let [uv0, uv1, uv2] = triangle.map(|(i, j)| self.vertex_uv(i, j));
+                let b0 = 1.0 - b1 - b2;
+                let u = b0 * uv0.0 + b1 * uv1.0 + b2 * uv2.0;
+                let v = b0 * uv0.1 + b1 * uv1.1 + b2 * uv2.1;
+                if self.material.alpha_test(u, v, &ray.at(t)) {
+                    closest = Some((t, b1, b2, index));
+                }
+            }
+        }
+
+        let (t, b1, b2, index) = closest?;
+        let triangle = triangles[index];
+        let b0 = 1.0 - b1 - b2;
+        let [v0, v1, v2] = triangle.map(|(i, j)| self.vertex(i, j));
+        let [n0, n1, n2] = triangle.map(|(i, j)| self.normals[j * self.resolution_x + i]);
+        let [uv0, uv1, uv2] = triangle.map(|(i, j)| self.vertex_uv(i, j));
+
+        let geometric_normal = (v1 - v0).cross(v2 - v0);
+        let (_, front_face) = HitRecord::orient_towards_ray(ray, geometric_normal);
+        let shading_normal = (b0 * n0 + b1 * n1 + b2 * n2).normalized();
+        let (tangent, bitangent) =
+            triangle::tangent_frame([v0, v1, v2], [uv0, uv1, uv2], shading_normal);
+        let footprint = HitRecord::texture_footprint(
+            ray,
+            t,
+            geometric_normal.normalized(),
+            triangle::area_per_uv([v0, v1, v2], [uv0, uv1, uv2]),
+        );
+
+        Some(HitRecord {
+            point: ray.at(t),
+            normal: if front_face {
+                shading_normal
+            } else {
+                -shading_normal
+            },
+            t,
+            front_face,
+            material: self.material.as_ref(),
+            u: b0 * uv0.0 + b1 * uv1.0 + b2 * uv2.0,
+            v: b0 * uv0.1 + b1 * uv1.1 + b2 * uv2.1,
+            tangent,
+            bitangent,
+            footprint,
+        })
+    }
+}
+
+impl Object for Heightfield<'_> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>> {
+        let (t_start, t_end) = self.bounding_box.hit_range(ray, t_min, t_max)?;
+
+        // Walk the grid cells the ray passes over with a 2D DDA.
+        let (dx, dz) = self.cell_size();
+        let cells_x = self.resolution_x - 1;
+        let cells_z = self.resolution_z - 1;
+        let origin = ray.origin();
+        let direction = ray.direction();
+
+        let start = ray.at(t_start) - self.corner;
+        let mut i = ((start.x() / dx).floor().max(0.0) as usize).min(cells_x - 1);
+        let mut j = ((start.z() / dz).floor().max(0.0) as usize).min(cells_z - 1);
+
+        let axis_setup = |index: usize, cell: f32, origin: f32, corner: f32, direction: f32| {
+            if direction == 0.0 {
+                return (f32::INFINITY, f32::INFINITY);
+            }
+            let next = if direction > 0.0 { index + 1 } else { index };
+            let boundary = corner + next as f32 * cell;
+            ((boundary - origin) / direction, cell / direction.abs())
+        };
+        let (mut t_next_x, t_delta_x) =
+            axis_setup(i, dx, origin.x(), self.corner.x(), direction.x());
+        let (mut t_next_z, t_delta_z) =
+            axis_setup(j, dz, origin.z(), self.corner.z(), direction.z());
+
+        let mut t_cell_start = t_start;
+        loop {
+            let t_cell_end = t_next_x.min(t_next_z).min(t_end);
+
+            // Skip cells the ray passes entirely above or below
+            let cell_heights = [
+                self.height(i, j),
+                self.height(i + 1, j),
+                self.height(i, j + 1),
+                self.height(i + 1, j + 1),
+            ];
+            let cell_min = cell_heights.iter().fold(f32::INFINITY, |a, &b| a.min(b));
+            let cell_max = cell_heights
+                .iter()
+                .fold(f32::NEG_INFINITY, |a, &b| a.max(b));
+            let y_start = ray.at(t_cell_start).y() - self.corner.y();
+            let y_end = ray.at(t_cell_end).y() - self.corner.y();
+            if y_start.min(y_end) <= cell_max && y_start.max(y_end) >= cell_min {
+                if let Some(rec) = self.hit_cell(ray, i, j, t_min, t_max) {
+                    return Some(rec);
+                }
+            }
+
+            if t_cell_end >= t_end {
+                return None;
+            }
+
+            if t_next_x < t_next_z {
+                if direction.x() > 0.0 {
+                    i += 1;
+                    if i >= cells_x {
+                        return None;
+                    }
+                } else {
+                    if i == 0 {
+                        return None;
+                    }
+                    i -= 1;
+                }
+                t_next_x += t_delta_x;
+            } else {
+                if direction.z() > 0.0 {
+                    j += 1;
+                    if j >= cells_z {
+                        return None;
+                    }
+                } else {
+                    if j == 0 {
+                        return None;
+                    }
+                    j -= 1;
+                }
+                t_next_z += t_delta_z;
+            }
+            t_cell_start = t_cell_end;
+        }
+    }
+
+    fn bounding_box(&self, _timeframe: Time) -> Option<AABB> {
+        Some(self.bounding_box)
+    }
+}
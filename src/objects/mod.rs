@@ -2,22 +2,31 @@ use dyn_clonable::*;
 
 mod bvh_node;
 mod constant_medium;
+mod csg;
 mod cuboid;
+mod curve;
+mod heightfield;
 mod object_list;
 mod rectangle;
+mod sdf_object;
 mod sphere;
 mod triangle;
 
 pub use bvh_node::BvhNode;
 pub use constant_medium::ConstantMedium;
+pub use csg::Csg;
 pub use cuboid::Cuboid;
+pub use curve::{Curve, CurveType};
+pub use heightfield::{Heightfield, HeightfieldError};
 pub use object_list::ObjectList;
-pub use rectangle::Rectangle;
+pub use rectangle::{Rectangle, RectangleError};
+pub use sdf_object::SdfObject;
 pub use sphere::Sphere;
 pub use triangle::Triangle;
 
 use crate::{aabb::AABB, materials::Material, ray::Ray, time::Time, vec3::Vec3};
 
+#[derive(Clone, Copy)]
 pub struct HitRecord<'a> {
     pub point: Vec3,
     pub normal: Vec3,
@@ -26,6 +35,15 @@ pub struct HitRecord<'a> {
     pub material: &'a dyn Material,
     pub u: f32,
     pub v: f32,
+    /// Unit surface tangent along the direction of increasing `u`.
+    /// Zero if the object doesn't define one.
+    pub tangent: Vec3,
+    /// Unit surface tangent along the direction of increasing `v`, perpendicular to `tangent`.
+    /// Zero if the object doesn't define one.
+    pub bitangent: Vec3,
+    /// Width of the ray's footprint on the surface in texture coordinates, which textures
+    /// filter over. Zero for an unfiltered lookup.
+    pub footprint: f32,
 }
 
 impl HitRecord<'_> {
@@ -42,11 +60,43 @@ impl HitRecord<'_> {
 
         (normal, front_face)
     }
+
+    /// Width in texture coordinates of the footprint of `ray` on a surface hit at `t`,
+    /// where `area_per_uv` is the surface area covered by a unit square of texture
+    /// coordinates. The footprint stretches along the surface at grazing angles, which is
+    /// averaged into a square of the same area.
+    pub fn texture_footprint(ray: &Ray, t: f32, normal: Vec3, area_per_uv: f32) -> f32 {
+        let width = ray.footprint(t);
+        if width <= 0.0 || area_per_uv <= 0.0 {
+            return 0.0;
+        }
+
+        let cos_theta = ray.direction().normalized().dot(normal).abs().max(1e-3);
+        width / (cos_theta * area_per_uv).sqrt()
+    }
 }
 
 #[clonable]
 pub trait Object: Clone {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord>;
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>>;
+
+    /// Returns every crossing of the ray with the object's surface in
+    /// `t_min..t_max`, ordered by `t`.
+    /// Entries and exits can be told apart by `front_face`, which makes it
+    /// possible to reconstruct the intervals the ray spends inside the object.
+    fn hit_all(&self, ray: &Ray, t_min: f32, t_max: f32) -> Vec<HitRecord<'_>> {
+        let mut hits = Vec::new();
+        let mut t_min = t_min;
+
+        while let Some(rec) = self.hit(ray, t_min, t_max) {
+            // Step past the current hit, relative to its magnitude so we don't get stuck far away.
+            t_min = rec.t + 0.0001 * rec.t.abs().max(1.0);
+            hits.push(rec);
+        }
+
+        hits
+    }
+
     fn bounding_box(&self, timeframe: Time) -> Option<AABB>;
 }
 
@@ -55,5 +105,6 @@ pub type BoxedObject<'a> = Box<dyn Object + Send + Sync + 'a>;
 pub trait Transformable {
     fn translate(self, offset: Vec3) -> Self;
     fn rotate(self, axis: Vec3, angle_rad: f32) -> Self;
+    #[allow(dead_code)]
     fn scale(self, factor: f32) -> Self;
 }
@@ -22,7 +22,7 @@ impl<'a> ObjectList<'a> {
 }
 
 impl Object for ObjectList<'_> {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>> {
         let mut hit_record = None;
         let mut closest_so_far = t_max;
 
@@ -8,7 +8,7 @@ use crate::{
     vec3::{Point3, Vec3},
 };
 
-use super::{triangle::Triangle, HitRecord, Object, Transformable};
+use super::{HitRecord, Object, Transformable, Triangle};
 
 #[derive(Error, Debug)]
 pub enum RectangleError {
@@ -38,12 +38,34 @@ impl<'a> Rectangle<'a> {
 
         let material = Box::new(material);
         let material = material as BoxedMaterial<'a>;
+        // Texture coordinates run from p0 to p1 along u and from p0 to p3 along v
         Ok(Rectangle {
-            triangle1: Triangle::new(p0, p1, p2, material.clone()),
-            triangle2: Triangle::new(p0, p2, p3, material),
+            triangle1: Triangle::new(p0, p1, p2, material.clone()).with_uvs([
+                (0.0, 0.0),
+                (1.0, 0.0),
+                (1.0, 1.0),
+            ]),
+            triangle2: Triangle::new(p0, p2, p3, material).with_uvs([
+                (0.0, 0.0),
+                (1.0, 1.0),
+                (0.0, 1.0),
+            ]),
         })
     }
 
+    /// Repeats the texture coordinates `u_count` and `v_count` times across the rectangle,
+    /// which tiles textures that wrap around.
+    pub fn with_tiling(self, u_count: f32, v_count: f32) -> Self {
+        Self {
+            triangle1: self
+                .triangle1
+                .with_uvs([(0.0, 0.0), (u_count, 0.0), (u_count, v_count)]),
+            triangle2: self
+                .triangle2
+                .with_uvs([(0.0, 0.0), (u_count, v_count), (0.0, v_count)]),
+        }
+    }
+
     pub fn from_points(
         points: [Point3; 4],
         material: impl Material + 'a + Send + Sync,
@@ -101,7 +123,7 @@ impl<'a> Rectangle<'a> {
 }
 
 impl Object for Rectangle<'_> {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>> {
         self.triangle1
             .hit(ray, t_min, t_max)
             .or_else(|| self.triangle2.hit(ray, t_min, t_max))
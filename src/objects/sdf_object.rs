@@ -0,0 +1,118 @@
+use crate::{
+    aabb::AABB,
+    materials::{BoxedMaterial, Material},
+    ray::Ray,
+    sdf::{BoxedSdf, Sdf},
+    time::Time,
+    vec3::{Point3, Vec3},
+};
+
+use super::{HitRecord, Object};
+
+const MAX_STEPS: usize = 512;
+const SURFACE_DISTANCE: f32 = 1e-4;
+const NORMAL_OFFSET: f32 = 1e-4;
+
+/// Object defined by a signed distance function, intersected by sphere tracing.
+///
+/// Distance functions don't have a natural extent, so the bounding box has to be
+/// supplied and must contain the whole surface.
+#[derive(Clone)]
+pub struct SdfObject<'a> {
+    sdf: BoxedSdf<'a>,
+    bounding_box: AABB,
+    material: BoxedMaterial<'a>,
+    step_scale: f32,
+}
+
+impl<'a> SdfObject<'a> {
+    pub fn new(
+        sdf: impl Sdf + Send + Sync + 'a,
+        bounding_box: AABB,
+        material: impl Material + Send + Sync + 'a,
+    ) -> Self {
+        Self::new_boxed(Box::new(sdf), bounding_box, Box::new(material))
+    }
+
+    pub fn new_boxed(sdf: BoxedSdf<'a>, bounding_box: AABB, material: BoxedMaterial<'a>) -> Self {
+        Self {
+            sdf,
+            bounding_box,
+            material,
+            step_scale: 1.0,
+        }
+    }
+
+    /// Scales every marching step, which is needed for distance functions that overestimate
+    /// the distance to the surface, such as twisted ones.
+    pub fn with_step_scale(self, step_scale: f32) -> Self {
+        Self { step_scale, ..self }
+    }
+
+    /// Estimates the surface normal from the gradient of the distance function,
+    /// using the tetrahedron technique to get away with four samples.
+    fn normal(&self, point: &Point3) -> Vec3 {
+        let offsets = [
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(-1.0, -1.0, 1.0),
+            Vec3::new(-1.0, 1.0, -1.0),
+            Vec3::new(1.0, 1.0, 1.0),
+        ];
+
+        offsets
+            .iter()
+            .map(|k| k * self.sdf.distance(&(point + NORMAL_OFFSET * k)))
+            .fold(Vec3::zero(), |acc, v| acc + v)
+            .normalized()
+    }
+}
+
+impl Object for SdfObject<'_> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>> {
+        let (t_start, t_end) = self.bounding_box.hit_range(ray, t_min, t_max)?;
+        let ray_length = ray.direction().length();
+
+        // A ray leaving the surface starts right on it, so we first have to step off of it
+        // before we can look for the next crossing.
+        let mut leaving_surface = true;
+        let mut t = t_start;
+        for _ in 0..MAX_STEPS {
+            if t >= t_end {
+                return None;
+            }
+
+            let distance = self.sdf.distance(&ray.at(t)).abs();
+            if distance >= SURFACE_DISTANCE {
+                leaving_surface = false;
+            } else if !leaving_surface {
+                let point = ray.at(t);
+                // A cut out crossing is marched through like the surface being left
+                if self.material.alpha_test(0.0, 0.0, &point) {
+                    let (normal, front_face) =
+                        HitRecord::orient_towards_ray(ray, self.normal(&point));
+                    return Some(HitRecord {
+                        point,
+                        normal,
+                        t,
+                        front_face,
+                        material: self.material.as_ref(),
+                        u: 0.0,
+                        v: 0.0,
+                        tangent: Vec3::zero(),
+                        bitangent: Vec3::zero(),
+                        footprint: 0.0,
+                    });
+                }
+                leaving_surface = true;
+            }
+
+            t += (self.step_scale * distance).max(SURFACE_DISTANCE) / ray_length;
+        }
+
+        None
+    }
+
+    fn bounding_box(&self, _timeframe: Time) -> Option<AABB> {
+        Some(self.bounding_box)
+    }
+}
@@ -1,6 +1,8 @@
+use std::f32::consts::PI;
+
 use crate::{
     aabb::AABB,
-    materials::{BoxedMaterial, Material},
+    materials::{BoxedMaterial, Material, Onb},
     ray::Ray,
     time::Time,
     vec3::{Point3, Vec3},
@@ -61,8 +63,6 @@ impl<'a> Sphere<'a> {
 
     // Compute the UV coordinates of a point on the surface of a unit sphere.
     pub fn sphere_uv(&self, p: &Vec3) -> (f32, f32) {
-        const PI: f32 = std::f32::consts::PI;
-
         // x = -cos(phi) * sin(theta)
         // y = -cos(theta)
         // z = sin(phi) * sin(theta)
@@ -76,10 +76,28 @@ impl<'a> Sphere<'a> {
         let v = theta / PI;
         (u, v)
     }
-}
 
-impl Object for Sphere<'_> {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+    /// Computes the unit tangent and bitangent along increasing `u` and `v` at a point
+    /// on the surface of a unit sphere.
+    fn sphere_tangents(&self, p: &Vec3) -> (Vec3, Vec3) {
+        let local = p.rotate(self.rotation, self.rotation.length());
+
+        // Derivative of the point with respect to phi, see `sphere_uv`
+        let tangent = Vec3::new(local.z(), 0.0, -local.x());
+        if tangent.near_zero() {
+            // At the poles
+            let basis = Onb::from_w(*p);
+            return (basis.u, basis.v);
+        }
+
+        let tangent = tangent
+            .rotate(self.rotation, -self.rotation.length())
+            .normalized();
+        (tangent, p.cross(tangent))
+    }
+
+    /// Returns both ray parameters at which the ray crosses the sphere, nearest first.
+    fn roots(&self, ray: &Ray) -> Option<(f32, f32)> {
         let oc = ray.origin() - self.movement.at(ray.time());
         let a = ray.direction().length_squared();
         let half_b = oc.dot(ray.direction());
@@ -92,33 +110,56 @@ impl Object for Sphere<'_> {
 
         let sqrt_disc = discriminant.sqrt();
 
-        let calc_hit_record = |root: f32| {
-            let p = ray.at(root);
-            let normal = (p - self.movement.at(ray.time())) / self.radius;
-            let (normal, front_face) = HitRecord::orient_towards_ray(ray, normal);
-            let (u, v) = self.sphere_uv(&normal);
-            Some(HitRecord {
-                point: p,
-                normal,
-                t: root,
-                front_face,
-                material: self.material.as_ref(),
-                u,
-                v,
-            })
-        };
+        Some(((-half_b - sqrt_disc) / a, (-half_b + sqrt_disc) / a))
+    }
 
-        let root = (-half_b - sqrt_disc) / a;
-        if (t_min..t_max).contains(&root) {
-            return calc_hit_record(root);
+    fn hit_record(&self, ray: &Ray, root: f32) -> HitRecord<'_> {
+        let p = ray.at(root);
+        let outward_normal = (p - self.movement.at(ray.time())) / self.radius;
+        let (normal, front_face) = HitRecord::orient_towards_ray(ray, outward_normal);
+        let (u, v) = self.sphere_uv(&outward_normal);
+        let (tangent, bitangent) = self.sphere_tangents(&outward_normal);
+        // u runs around the sphere's circumference and v over half of it, so a unit square
+        // of texture coordinates covers 2π² r² sin θ, which vanishes at the poles
+        let area_per_uv = 2.0 * PI * PI * self.radius * self.radius * (PI * v).sin();
+        let footprint = HitRecord::texture_footprint(ray, root, outward_normal, area_per_uv);
+        HitRecord {
+            point: p,
+            normal,
+            t: root,
+            front_face,
+            material: self.material.as_ref(),
+            u,
+            v,
+            tangent,
+            bitangent,
+            footprint,
         }
+    }
+}
 
-        let root = (-half_b + sqrt_disc) / a;
-        if (t_min..t_max).contains(&root) {
-            return calc_hit_record(root);
-        }
+impl Object for Sphere<'_> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>> {
+        let (near, far) = self.roots(ray)?;
+
+        [near, far]
+            .into_iter()
+            .filter(|root| (t_min..t_max).contains(root))
+            .map(|root| self.hit_record(ray, root))
+            .find(|hit| hit.material.alpha_test(hit.u, hit.v, &hit.point))
+    }
+
+    fn hit_all(&self, ray: &Ray, t_min: f32, t_max: f32) -> Vec<HitRecord<'_>> {
+        let Some((near, far)) = self.roots(ray) else {
+            return Vec::new();
+        };
 
-        None
+        [near, far]
+            .into_iter()
+            .filter(|root| (t_min..t_max).contains(root))
+            .map(|root| self.hit_record(ray, root))
+            .filter(|hit| hit.material.alpha_test(hit.u, hit.v, &hit.point))
+            .collect()
     }
 
     fn bounding_box(&self, timeframe: Time) -> Option<crate::aabb::AABB> {
@@ -1,4 +1,9 @@
-use crate::{aabb::AABB, materials::BoxedMaterial, ray::Ray, vec3::Vec3};
+use crate::{
+    aabb::AABB,
+    materials::{BoxedMaterial, Onb},
+    ray::Ray,
+    vec3::Vec3,
+};
 
 use super::{HitRecord, Object, Transformable};
 
@@ -7,6 +12,7 @@ pub struct Triangle<'a> {
     v0: Vec3,
     v1: Vec3,
     v2: Vec3,
+    uvs: [(f32, f32); 3],
     material: BoxedMaterial<'a>,
 }
 
@@ -16,44 +22,123 @@ impl<'a> Triangle<'a> {
             v0,
             v1,
             v2,
+            uvs: [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)],
             material,
         }
     }
+
+    /// Sets the texture coordinates of the three vertices.
+    pub fn with_uvs(self, uvs: [(f32, f32); 3]) -> Self {
+        Self { uvs, ..self }
+    }
 }
 
-impl Object for Triangle<'_> {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
-        // Möller–Trumbore intersection algorithm
-        // Reference: https://en.wikipedia.org/wiki/M%C3%B6ller%E2%80%93Trumbore_intersection_algorithm
-        let e1 = self.v1 - self.v0;
-        let e2 = self.v2 - self.v0;
-
-        let h = ray.direction().cross(e2);
-        let a = e1.dot(h);
-        if a.abs() < 1e-8 {
-            return None;
-        }
+/// Computes the unit tangent and bitangent of a triangle, which point along increasing `u`
+/// and `v` of its texture coordinates, projected onto the plane perpendicular to `normal`.
+pub(super) fn tangent_frame(
+    vertices: [Vec3; 3],
+    uvs: [(f32, f32); 3],
+    normal: Vec3,
+) -> (Vec3, Vec3) {
+    let e1 = vertices[1] - vertices[0];
+    let e2 = vertices[2] - vertices[0];
+    let (du1, dv1) = (uvs[1].0 - uvs[0].0, uvs[1].1 - uvs[0].1);
+    let (du2, dv2) = (uvs[2].0 - uvs[0].0, uvs[2].1 - uvs[0].1);
 
-        let f = 1.0 / a;
-        let s = ray.origin() - self.v0;
-        let u = f * s.dot(h);
-        if !(0.0..1.0).contains(&u) {
-            return None;
-        }
+    let determinant = du1 * dv2 - du2 * dv1;
+    if determinant.abs() < 1e-12 {
+        let basis = Onb::from_w(normal);
+        return (basis.u, basis.v);
+    }
 
-        let q = s.cross(e1);
-        let v = f * ray.direction().dot(q);
-        if !(0.0..(1.0 - u)).contains(&v) {
-            return None;
-        }
+    let dpdu = (dv2 * e1 - dv1 * e2) / determinant;
+    let dpdv = (du1 * e2 - du2 * e1) / determinant;
+
+    // Gram-Schmidt keeps the handedness of the texture mapping
+    let tangent = (dpdu - normal * normal.dot(dpdu)).normalized();
+    let bitangent = (dpdv - normal * normal.dot(dpdv) - tangent * tangent.dot(dpdv)).normalized();
+
+    (tangent, bitangent)
+}
+
+/// Surface area covered by a unit square of the triangle's texture coordinates.
+pub(super) fn area_per_uv(vertices: [Vec3; 3], uvs: [(f32, f32); 3]) -> f32 {
+    let (du1, dv1) = (uvs[1].0 - uvs[0].0, uvs[1].1 - uvs[0].1);
+    let (du2, dv2) = (uvs[2].0 - uvs[0].0, uvs[2].1 - uvs[0].1);
+    let uv_area = (du1 * dv2 - du2 * dv1).abs();
+    if uv_area < 1e-12 {
+        return 0.0;
+    }
+
+    (vertices[1] - vertices[0])
+        .cross(vertices[2] - vertices[0])
+        .length()
+        / uv_area
+}
+
+/// Intersects the ray with the triangle spanned by the given vertices.
+/// Returns the ray parameter and the barycentric coordinates of `v1` and `v2` at the hit.
+pub(super) fn intersect(
+    ray: &Ray,
+    v0: Vec3,
+    v1: Vec3,
+    v2: Vec3,
+    t_min: f32,
+    t_max: f32,
+) -> Option<(f32, f32, f32)> {
+    // Möller–Trumbore intersection algorithm
+    // Reference: https://en.wikipedia.org/wiki/M%C3%B6ller%E2%80%93Trumbore_intersection_algorithm
+    let e1 = v1 - v0;
+    let e2 = v2 - v0;
+
+    let h = ray.direction().cross(e2);
+    let a = e1.dot(h);
+    if a.abs() < 1e-8 {
+        return None;
+    }
 
-        let t = f * e2.dot(q);
-        if !(t_min..t_max).contains(&t) {
+    let f = 1.0 / a;
+    let s = ray.origin() - v0;
+    let u = f * s.dot(h);
+    if !(0.0..1.0).contains(&u) {
+        return None;
+    }
+
+    let q = s.cross(e1);
+    let v = f * ray.direction().dot(q);
+    if !(0.0..(1.0 - u)).contains(&v) {
+        return None;
+    }
+
+    let t = f * e2.dot(q);
+    if !(t_min..t_max).contains(&t) {
+        return None;
+    }
+
+    Some((t, u, v))
+}
+
+impl Object for Triangle<'_> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord<'_>> {
+        let (t, b1, b2) = intersect(ray, self.v0, self.v1, self.v2, t_min, t_max)?;
+        let b0 = 1.0 - b1 - b2;
+        let [uv0, uv1, uv2] = self.uvs;
+
+        let point = ray.at(t);
+        let u = b0 * uv0.0 + b1 * uv1.0 + b2 * uv2.0;
+        let v = b0 * uv0.1 + b1 * uv1.1 + b2 * uv2.1;
+        if !self.material.alpha_test(u, v, &point) {
             return None;
         }
 
-        let point = ray.at(t);
-        let normal = e1.cross(e2).normalized();
+        let normal = (self.v1 - self.v0).cross(self.v2 - self.v0).normalized();
+        let (tangent, bitangent) = tangent_frame([self.v0, self.v1, self.v2], self.uvs, normal);
+        let footprint = HitRecord::texture_footprint(
+            ray,
+            t,
+            normal,
+            area_per_uv([self.v0, self.v1, self.v2], self.uvs),
+        );
         let (normal, front_face) = HitRecord::orient_towards_ray(ray, normal);
         Some(super::HitRecord {
             point,
@@ -63,6 +148,9 @@ impl Object for Triangle<'_> {
             material: &*self.material,
             u,
             v,
+            tangent,
+            bitangent,
+            footprint,
         })
     }
 
@@ -88,28 +176,29 @@ impl Object for Triangle<'_> {
 
 impl Transformable for Triangle<'_> {
     fn translate(self, offset: Vec3) -> Self {
-        Self::new(
-            self.v0 + offset,
-            self.v1 + offset,
-            self.v2 + offset,
-            self.material,
-        )
+        Self {
+            v0: self.v0 + offset,
+            v1: self.v1 + offset,
+            v2: self.v2 + offset,
+            ..self
+        }
     }
 
     fn rotate(self, axis: Vec3, angle_rad: f32) -> Self {
-        let v0 = self.v0.rotate(axis, angle_rad);
-        let v1 = self.v1.rotate(axis, angle_rad);
-        let v2 = self.v2.rotate(axis, angle_rad);
-
-        Self::new(v0, v1, v2, self.material)
+        Self {
+            v0: self.v0.rotate(axis, angle_rad),
+            v1: self.v1.rotate(axis, angle_rad),
+            v2: self.v2.rotate(axis, angle_rad),
+            ..self
+        }
     }
 
     fn scale(self, factor: f32) -> Self {
-        Self::new(
-            self.v0 * factor,
-            self.v1 * factor,
-            self.v2 * factor,
-            self.material,
-        )
+        Self {
+            v0: self.v0 * factor,
+            v1: self.v1 * factor,
+            v2: self.v2 * factor,
+            ..self
+        }
     }
 }
@@ -1,5 +1,6 @@
 pub(crate) mod rand {
     use rand::distributions::Standard;
+    #[allow(unused_imports)]
     pub use rand::*;
 
     pub fn random_range<T>(min: T, max: T) -> T
@@ -9,4 +10,10 @@ pub(crate) mod rand {
     {
         min + (max - min) * rand::random::<T>()
     }
+
+    /// Samples a normally distributed value using the Box-Muller transform.
+    pub fn standard_normal() -> f32 {
+        let (r1, r2): (f32, f32) = rand::random();
+        (-2.0 * (1.0 - r1).ln()).sqrt() * (2.0 * std::f32::consts::PI * r2).cos()
+    }
 }
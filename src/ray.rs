@@ -5,6 +5,12 @@ pub struct Ray {
     origin: Point3,
     direction: Vec3,
     time: f32,
+    /// Hero wavelength in nanometers when rendering spectrally.
+    wavelength: Option<f32>,
+    /// Width of the cone around the ray at its origin, and the angle by which it widens per
+    /// unit of distance, which estimate how much of a surface a ray stands for.
+    cone_width: f32,
+    cone_spread: f32,
 }
 
 impl Ray {
@@ -17,6 +23,21 @@ impl Ray {
             origin,
             direction,
             time,
+            wavelength: None,
+            cone_width: 0.0,
+            cone_spread: 0.0,
+        }
+    }
+
+    pub fn with_wavelength(self, wavelength: Option<f32>) -> Self {
+        Self { wavelength, ..self }
+    }
+
+    pub fn with_cone(self, width: f32, spread: f32) -> Self {
+        Self {
+            cone_width: width,
+            cone_spread: spread,
+            ..self
         }
     }
 
@@ -32,10 +53,23 @@ impl Ray {
         self.time
     }
 
+    pub fn wavelength(&self) -> Option<f32> {
+        self.wavelength
+    }
+
     pub fn at(&self, t: f32) -> Point3 {
         self.origin + t * self.direction
     }
 
+    pub fn cone_spread(&self) -> f32 {
+        self.cone_spread
+    }
+
+    /// Width of the cone around the ray at `at(t)`, zero for rays without a cone.
+    pub fn footprint(&self, t: f32) -> f32 {
+        self.cone_width + self.cone_spread * t * self.direction.length()
+    }
+
     pub fn translate(self, offset: Vec3) -> Self {
         Self::new(self.origin + offset, self.direction)
     }
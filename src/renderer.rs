@@ -8,18 +8,24 @@ use crate::{
 pub struct Renderer {
     samples_per_pixel: usize,
     max_depth: usize,
+    spectral: bool,
 }
 
 impl Renderer {
-    pub fn new(samples_per_pixel: usize, max_depth: usize) -> Self {
+    pub fn new(samples_per_pixel: usize, max_depth: usize, spectral: bool) -> Self {
         Self {
             samples_per_pixel,
             max_depth,
+            spectral,
         }
     }
 
     pub fn from_settings(settings: RendererSettings) -> Self {
-        Self::new(settings.samples_per_pixel, settings.max_depth)
+        Self::new(
+            settings.samples_per_pixel,
+            settings.max_depth,
+            settings.spectral,
+        )
     }
 
     pub fn render_image(
@@ -46,7 +52,11 @@ impl Renderer {
 
                     let ray = camera.get_ray(u, v);
 
-                    pixel_color += world.ray_color(&ray, self.max_depth);
+                    pixel_color += if self.spectral {
+                        world.ray_color_spectral(&ray, self.max_depth)
+                    } else {
+                        world.ray_color(&ray, self.max_depth)
+                    };
                 }
 
                 *pixel = pixel_color / self.samples_per_pixel as f32;
@@ -0,0 +1,48 @@
+use crate::{
+    color::Color,
+    materials::{Lambertian, Metal, NormalMapped},
+    objects::{Object, ObjectList, Rectangle, Sphere},
+    settings::{ColorSpace, TextureSettings},
+    textures::{Checker, ImageTexture, NoiseTexture, TextureError},
+    vec3::Point3,
+};
+
+pub fn new() -> Result<impl Object, TextureError> {
+    let mut objects = ObjectList::new(vec![]);
+
+    // Tiled floor from a tangent space normal map
+    let floor = NormalMapped::from_normal_map(
+        Lambertian::new(Color::new(0.6, 0.6, 0.6)),
+        ImageTexture::from_settings(
+            "assets/normal_map.png",
+            TextureSettings {
+                color_space: ColorSpace::Linear,
+                ..Default::default()
+            },
+        )?,
+    );
+    objects.add(Rectangle::new_xz(-6.0, 6.0, -6.0, 6.0, 0.0, floor));
+
+    // Marble and hammered metal bumped by the same procedural noise
+    let noise = NoiseTexture::new(4.0);
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -2.2),
+        1.0,
+        NormalMapped::from_bump_map(Lambertian::from_texture(noise.clone()), noise.clone(), 0.05),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 0.0),
+        1.0,
+        NormalMapped::from_bump_map(Metal::new(Color::new(0.8, 0.6, 0.2), 0.0), noise, 0.05),
+    ));
+
+    // Grooves along the edges of a procedural checker pattern
+    let grooves = Checker::from_colors(Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 2.2),
+        1.0,
+        NormalMapped::from_bump_map(Lambertian::new(Color::new(0.1, 0.2, 0.5)), grooves, 0.01),
+    ));
+
+    Ok(objects)
+}
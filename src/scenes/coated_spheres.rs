@@ -0,0 +1,42 @@
+use crate::{
+    color::Color,
+    materials::{Coated, Conductor, Lambertian},
+    objects::{Object, ObjectList, Sphere},
+    textures::{Checker, NoiseTexture},
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    // Glossy paint
+    let paint = Coated::new(Lambertian::new(Color::new(0.6, 0.02, 0.02)), 1.5);
+    objects.add(Sphere::new(Point3::new(0.0, 1.0, -3.3), 1.0, paint));
+
+    // Metallic paint, a rough metal base under a smooth coat
+    let metallic_paint =
+        Coated::new(Conductor::aluminium(0.4), 1.5).with_absorption(Color::new(0.3, 0.5, 0.9));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -1.1),
+        1.0,
+        metallic_paint,
+    ));
+
+    // Varnished wood, with a coat that yellows what it covers
+    let wood = Coated::new(Lambertian::from_texture(NoiseTexture::new(8.0)), 1.5)
+        .with_absorption(Color::new(0.9, 0.7, 0.4));
+    objects.add(Sphere::new(Point3::new(0.0, 1.0, 1.1), 1.0, wood));
+
+    // Glazed ceramic
+    let ceramic = Coated::new(Lambertian::new(Color::new(0.9, 0.9, 0.85)), 1.5);
+    objects.add(Sphere::new(Point3::new(0.0, 1.0, 3.3), 1.0, ceramic));
+
+    objects
+}
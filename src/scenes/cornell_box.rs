@@ -1,15 +1,22 @@
 use crate::{
     color::Color,
-    materials::{DiffuseLight, Lambertian},
+    lights::{AreaLight, AreaLightError, Light},
+    materials::{DiffuseLight, Lambertian, LightPower},
     objects::{Cuboid, Object, ObjectList, Rectangle, Transformable},
     vec3::{Point3, Vec3},
 };
 
-pub fn new() -> impl Object {
+pub fn new(lights: &mut Vec<Light>) -> Result<impl Object, AreaLightError> {
     let red = Lambertian::new(Color::new(0.65, 0.05, 0.05));
     let white = Lambertian::new(Color::new(0.73, 0.73, 0.73));
     let green = Lambertian::new(Color::new(0.12, 0.45, 0.15));
-    let light = DiffuseLight::from_color(Color::new(15.0, 15.0, 15.0));
+    let light = AreaLight::new(
+        Point3::new(213.0, 554.0, 227.0),
+        Vec3::new(130.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 105.0),
+        DiffuseLight::from_color(Color::new(1.0, 1.0, 1.0)).one_sided(),
+    )?
+    .with_power(LightPower::Watts(640_000.0))?;
 
     let mut objects = ObjectList::new(vec![]);
     objects.add(Rectangle::new_yz(
@@ -21,14 +28,8 @@ pub fn new() -> impl Object {
         green.clone(),
     ));
     objects.add(Rectangle::new_yz(0.0, 555.0, 0.0, 555.0, 0.0, red.clone()));
-    objects.add(Rectangle::new_xz(
-        213.0,
-        343.0,
-        227.0,
-        332.0,
-        554.0,
-        light.clone(),
-    ));
+    objects.add(light.object());
+    lights.push(light.into());
     objects.add(Rectangle::new_xz(
         0.0,
         555.0,
@@ -74,5 +75,5 @@ pub fn new() -> impl Object {
 
     objects.add(box2);
 
-    objects
+    Ok(objects)
 }
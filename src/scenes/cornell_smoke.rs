@@ -1,17 +1,24 @@
 use crate::{
     color::Color,
-    materials::{DiffuseLight, Lambertian},
+    lights::{AreaLight, AreaLightError, Light},
+    materials::{DiffuseLight, Lambertian, LightPower},
     objects::{ConstantMedium, Cuboid, Object, ObjectList, Rectangle, Transformable},
     vec3::{Point3, Vec3},
 };
 
-pub fn new() -> impl Object {
+pub fn new(lights: &mut Vec<Light>) -> Result<impl Object, AreaLightError> {
     let mut objects = ObjectList::new(vec![]);
 
     let red = Lambertian::new(Color::new(0.65, 0.05, 0.05));
     let white = Lambertian::new(Color::new(0.73, 0.73, 0.73));
     let green = Lambertian::new(Color::new(0.12, 0.45, 0.15));
-    let light = DiffuseLight::from_color(Color::new(7.0, 7.0, 7.0));
+    let light = AreaLight::new(
+        Point3::new(113.0, 554.0, 127.0),
+        Vec3::new(330.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 305.0),
+        DiffuseLight::from_color(Color::new(1.0, 1.0, 1.0)).one_sided(),
+    )?
+    .with_power(LightPower::Lumens(1.5e9))?;
 
     objects.add(Rectangle::new_yz(
         0.0,
@@ -22,14 +29,8 @@ pub fn new() -> impl Object {
         green.clone(),
     ));
     objects.add(Rectangle::new_yz(0.0, 555.0, 0.0, 555.0, 0.0, red.clone()));
-    objects.add(Rectangle::new_xz(
-        113.,
-        443.,
-        127.,
-        432.,
-        554.,
-        light.clone(),
-    ));
+    objects.add(light.object());
+    lights.push(light.into());
     objects.add(Rectangle::new_xz(
         0.0,
         555.0,
@@ -82,5 +83,5 @@ pub fn new() -> impl Object {
         0.01,
     ));
 
-    objects
+    Ok(objects)
 }
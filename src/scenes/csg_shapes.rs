@@ -0,0 +1,45 @@
+use crate::{
+    color::Color,
+    materials::{Dielectric, Lambertian, Metal},
+    objects::{Csg, Cuboid, Object, ObjectList, Sphere},
+    textures::Checker,
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut world = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    world.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    // Biconvex lens facing the camera
+    let glass = Dielectric::new(1.5);
+    world.add(Csg::intersection(
+        Sphere::new(Point3::new(2.6, 1.0, 0.0), 3.0, glass.clone()),
+        Sphere::new(Point3::new(-2.6, 1.0, 0.0), 3.0, glass),
+    ));
+
+    // Cube with a spherical bite taken out of its corner
+    let red = Lambertian::new(Color::new(0.65, 0.05, 0.05));
+    world.add(Csg::difference(
+        Cuboid::bounded_by(
+            Point3::new(-1.0, 0.0, -3.5),
+            Point3::new(0.5, 1.5, -2.0),
+            red.clone(),
+        ),
+        Sphere::new(Point3::new(0.5, 1.5, -2.0), 0.9, red),
+    ));
+
+    // Two overlapping metal spheres
+    let metal = Metal::new(Color::new(0.7, 0.6, 0.5), 0.1);
+    world.add(Csg::union(
+        Sphere::new(Point3::new(-0.5, 0.7, 2.5), 0.7, metal.clone()),
+        Sphere::new(Point3::new(-0.5, 1.4, 3.0), 0.5, metal),
+    ));
+
+    world
+}
@@ -0,0 +1,61 @@
+use crate::{
+    color::Color,
+    materials::{Dielectric, Lambertian, Principled},
+    objects::{Cuboid, Object, ObjectList, Sphere},
+    vec3::Point3,
+};
+
+/// Open pavilion under the daylight sky, whose columns cast sharp sun shadows onto the
+/// floor and the back wall while the shaded parts are lit by the blue sky.
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::new(Color::new(0.3, 0.35, 0.2)),
+    ));
+
+    let concrete = Lambertian::new(Color::new(0.6, 0.58, 0.55));
+    let plaster = Lambertian::new(Color::new(0.8, 0.78, 0.74));
+
+    // Floor slab, roof and back wall
+    objects.add(Cuboid::bounded_by(
+        Point3::new(-3.0, 0.0, -4.0),
+        Point3::new(3.0, 0.2, 4.0),
+        concrete.clone(),
+    ));
+    objects.add(Cuboid::bounded_by(
+        Point3::new(-3.4, 3.2, -4.4),
+        Point3::new(3.4, 3.5, 4.4),
+        concrete.clone(),
+    ));
+    objects.add(Cuboid::bounded_by(
+        Point3::new(-3.0, 0.2, -4.0),
+        Point3::new(-2.7, 3.2, 4.0),
+        plaster,
+    ));
+
+    for z in [-3.6, -1.2, 1.2, 3.6] {
+        objects.add(Cuboid::bounded_by(
+            Point3::new(2.4, 0.2, z - 0.15),
+            Point3::new(2.7, 3.2, z + 0.15),
+            concrete.clone(),
+        ));
+    }
+
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 1.6),
+        0.8,
+        Principled::new(Color::new(0.7, 0.15, 0.1))
+            .with_roughness(0.2)
+            .with_clearcoat(1.0, 0.05),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -1.6),
+        0.8,
+        Dielectric::new(1.5),
+    ));
+
+    objects
+}
@@ -0,0 +1,54 @@
+use crate::{
+    color::Color,
+    lights::Light,
+    materials::{Lambertian, OrenNayar, Principled},
+    objects::{Object, ObjectList, Sphere},
+    vec3::{Point3, Vec3},
+};
+
+pub fn new(lights: &mut Vec<Light>) -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::new(Color::new(0.5, 0.5, 0.5)),
+    ));
+
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -2.2),
+        1.0,
+        Lambertian::new(Color::new(0.7, 0.3, 0.2)),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 0.0),
+        1.0,
+        Principled::new(Color::new(0.2, 0.4, 0.8)).with_roughness(0.3),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 2.2),
+        1.0,
+        OrenNayar::new(Color::new(0.8, 0.8, 0.7)),
+    ));
+
+    lights.extend([
+        // Low evening sun casting long, slightly soft shadows
+        Light::sun(
+            Vec3::new(1.0, 0.6, -1.5),
+            Color::new(2.0, 1.6, 1.2),
+            2f32.to_radians(),
+        ),
+        // Spotlight on the ground in front of the spheres
+        Light::spot(
+            Point3::new(6.0, 8.0, 4.0),
+            Point3::new(3.0, 0.0, 3.0),
+            Color::new(60.0, 60.0, 80.0),
+            10f32.to_radians(),
+            15f32.to_radians(),
+        ),
+        // Warm light between the spheres and the camera
+        Light::point(Point3::new(4.0, 1.5, -1.0), Color::new(6.0, 3.0, 1.0)),
+    ]);
+
+    objects
+}
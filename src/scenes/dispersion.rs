@@ -0,0 +1,75 @@
+use crate::{
+    color::Color,
+    materials::{BoxedMaterial, Dielectric, DiffuseLight, Ior, Lambertian},
+    objects::{Object, ObjectList, Rectangle, Sphere, Triangle},
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    let ground = Lambertian::new(Color::new(0.5, 0.5, 0.5));
+    objects.add(Rectangle::new_xz(-20.0, 20.0, -20.0, 20.0, 0.0, ground));
+
+    // Thin white stripes behind the glass, which fan out into rainbows when seen through it
+    let light = DiffuseLight::from_color(Color::new(6.0, 6.0, 6.0));
+    for i in 0..8 {
+        let y = 0.4 + 0.5 * i as f32;
+        objects.add(Rectangle::new_yz(
+            y - 0.03,
+            y + 0.03,
+            -6.0,
+            6.0,
+            -4.0,
+            light.clone(),
+        ));
+    }
+
+    // Prism lying along the z-axis, which spreads the colors vertically
+    let flint = Dielectric::from_ior(Ior::SF11);
+    let (front, back) = (2.5, -2.5);
+    let (a, b, c) = ((0.8, 0.3), (-0.8, 0.3), (0.0, 1.7));
+    let at = |(x, y): (f32, f32), z: f32| Point3::new(x, y, z);
+    objects.add(Triangle::new(
+        at(a, front),
+        at(b, front),
+        at(c, front),
+        Box::new(flint.clone()) as BoxedMaterial,
+    ));
+    objects.add(Triangle::new(
+        at(a, back),
+        at(c, back),
+        at(b, back),
+        Box::new(flint.clone()) as BoxedMaterial,
+    ));
+    for (p, q) in [(a, b), (b, c), (c, a)] {
+        objects.add(
+            Rectangle::new(
+                at(p, front),
+                at(p, back),
+                at(q, back),
+                at(q, front),
+                flint.clone(),
+            )
+            .unwrap(),
+        );
+    }
+
+    objects.add(Sphere::new(
+        Point3::new(2.0, 0.6, 3.5),
+        0.6,
+        Dielectric::from_ior(Ior::DIAMOND),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(2.0, 0.6, -3.5),
+        0.6,
+        Dielectric::from_ior(Ior::BK7),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(3.0, 0.4, 0.0),
+        0.4,
+        Dielectric::from_ior(Ior::FUSED_SILICA),
+    ));
+
+    objects
+}
@@ -0,0 +1,79 @@
+use crate::{
+    color::Color,
+    lights::{AreaLight, Light},
+    materials::{DiffuseLight, Lambertian, Principled},
+    objects::{Cuboid, Object, ObjectList, Rectangle, Sphere},
+    scenes::SceneError,
+    textures::{TextureCache, Tinted},
+    vec3::{Point3, Vec3},
+};
+
+/// Dark room lit only by a television and a neon sign, whose light is sampled by the
+/// brightness of their textures.
+pub fn new(
+    textures: &mut TextureCache,
+    lights: &mut Vec<Light>,
+) -> Result<impl Object, SceneError> {
+    let mut objects = ObjectList::new(vec![]);
+
+    objects.add(Rectangle::new_xz(
+        -1.0,
+        10.0,
+        -6.0,
+        6.0,
+        0.0,
+        Principled::new(Color::new(0.3, 0.18, 0.1)).with_roughness(0.3),
+    ));
+    objects.add(Rectangle::new_yz(
+        0.0,
+        4.0,
+        -6.0,
+        6.0,
+        0.0,
+        Lambertian::new(Color::new(0.6, 0.6, 0.65)),
+    ));
+
+    objects.add(Cuboid::bounded_by(
+        Point3::new(0.5, 0.8, -1.2),
+        Point3::new(0.8, 2.2, 1.2),
+        Principled::new(Color::new(0.02, 0.02, 0.02)).with_roughness(0.2),
+    ));
+    // The surfaces hit by rays are the same ones the lights sample
+    for light in [screen(textures)?, sign(textures)?] {
+        objects.add(light.object());
+        lights.push(light.into());
+    }
+
+    objects.add(Sphere::new(
+        Point3::new(2.5, 0.5, -1.0),
+        0.5,
+        Lambertian::new(Color::new(0.8, 0.8, 0.8)),
+    ));
+
+    Ok(objects)
+}
+
+/// Screen on the front of the television, facing the camera with the image upright.
+fn screen(textures: &mut TextureCache) -> Result<AreaLight, SceneError> {
+    let picture = Tinted::new(
+        textures.load("assets/earthmap.jpg")?,
+        Color::new(3.0, 3.0, 3.0),
+    );
+    Ok(AreaLight::new(
+        Point3::new(0.81, 0.9, 1.1),
+        Vec3::new(0.0, 0.0, -2.2),
+        Vec3::new(0.0, 1.2, 0.0),
+        DiffuseLight::from_texture(picture).one_sided(),
+    )?)
+}
+
+/// Neon sign on the wall, colored from a grayscale mask of its tubes.
+fn sign(textures: &mut TextureCache) -> Result<AreaLight, SceneError> {
+    let tubes = Tinted::new(textures.load("assets/neon.png")?, Color::new(8.0, 0.8, 4.0));
+    Ok(AreaLight::new(
+        Point3::new(0.02, 2.4, 3.6),
+        Vec3::new(0.0, 0.0, -2.0),
+        Vec3::new(0.0, 0.8, 0.0),
+        DiffuseLight::from_texture(tubes).one_sided(),
+    )?)
+}
@@ -0,0 +1,48 @@
+use crate::{
+    color::Color,
+    materials::{Dielectric, Lambertian, Metal, MixMaterial, Principled},
+    objects::{Object, ObjectList, Sphere},
+    vec3::Point3,
+};
+
+/// Spheres lit only by the environment map, from matte to mirror-like so both the light
+/// sampling and the material sampling of the environment show up.
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::new(Color::new(0.5, 0.5, 0.5)),
+    ));
+
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -3.3),
+        1.0,
+        Lambertian::new(Color::new(0.8, 0.8, 0.8)),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -1.1),
+        1.0,
+        Principled::new(Color::new(0.9, 0.6, 0.4))
+            .with_metallic(1.0)
+            .with_roughness(0.3),
+    ));
+    // Mirror lobe mixed with a diffuse one, like polished stone
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 1.1),
+        1.0,
+        MixMaterial::new(
+            Lambertian::new(Color::new(0.2, 0.3, 0.5)),
+            Metal::new(Color::new(0.9, 0.9, 0.9), 0.0),
+            0.3,
+        ),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 3.3),
+        1.0,
+        Dielectric::new(1.5),
+    ));
+
+    objects
+}
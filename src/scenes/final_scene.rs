@@ -5,12 +5,12 @@ use crate::{
     color::Color,
     materials::{Dielectric, DiffuseLight, Lambertian, Metal},
     objects::{BvhNode, ConstantMedium, Cuboid, Object, ObjectList, Rectangle, Sphere},
-    textures::{ImageTexture, NoiseTexture},
+    textures::{ImageTexture, NoiseTexture, TextureError},
     time::Time,
     vec3::{Point3, Vec3},
 };
 
-pub fn new(timeframe: Time) -> impl Object {
+pub fn new(timeframe: Time) -> Result<impl Object, TextureError> {
     let mut boxes = ObjectList::new(vec![]);
 
     let ground = Lambertian::new(Color::new(0.48, 0.83, 0.53));
@@ -74,7 +74,7 @@ pub fn new(timeframe: Time) -> impl Object {
         0.0001,
     ));
 
-    let emat = Lambertian::from_texture(ImageTexture::new("assets/earthmap.jpg"));
+    let emat = Lambertian::from_texture(ImageTexture::new("assets/earthmap.jpg")?);
     objects.add(Sphere::new(Point3::new(400.0, 200.0, 400.0), 100.0, emat));
     let pertext = NoiseTexture::new(0.1);
     objects.add(Sphere::new(
@@ -98,5 +98,5 @@ pub fn new(timeframe: Time) -> impl Object {
 
     objects.add(BvhNode::from_list(&boxes, timeframe));
 
-    objects
+    Ok(objects)
 }
@@ -0,0 +1,66 @@
+use crate::{
+    color::Color,
+    materials::{AlphaCutout, AlphaMode, DiffuseLight, Lambertian},
+    objects::{BvhNode, Cuboid, Object, ObjectList, Rectangle, Sphere, Transformable},
+    rand_ext::rand,
+    textures::{ImageTexture, TextureError},
+    time::Time,
+    vec3::{Point3, Vec3},
+};
+
+pub fn new(time: Time) -> Result<impl Object, TextureError> {
+    let mut objects = ObjectList::new(vec![]);
+
+    let ground = Lambertian::new(Color::new(0.35, 0.45, 0.25));
+    objects.add(Rectangle::new_xz(-20.0, 20.0, -20.0, 20.0, 0.0, ground));
+
+    // Fence made of a single quad, with sharp edges between the boards
+    let fence_texture = ImageTexture::new("assets/fence.png")?;
+    let fence = AlphaCutout::new(
+        Lambertian::from_texture(fence_texture.clone()),
+        fence_texture,
+        AlphaMode::Threshold(0.5),
+    );
+    objects.add(Rectangle::new_xy(-6.0, 6.0, 0.0, 1.5, 2.5, fence));
+
+    let trunk = Lambertian::new(Color::new(0.3, 0.2, 0.1));
+    objects.add(Cuboid::bounded_by(
+        Point3::new(-0.2, 0.0, -0.2),
+        Point3::new(0.2, 3.0, 0.2),
+        trunk,
+    ));
+
+    // Crown of randomly oriented leaf cards, blended at their soft edges
+    let leaf_texture = ImageTexture::new("assets/leaf.png")?;
+    let leaf = AlphaCutout::new(
+        Lambertian::from_texture(leaf_texture.clone()),
+        leaf_texture,
+        AlphaMode::Stochastic,
+    );
+    let mut leaves = ObjectList::new(vec![]);
+    for _ in 0..400 {
+        let center = Point3::new(0.0, 3.5, 0.0) + 1.5 * Vec3::random_in_unit_sphere();
+        let half_size = rand::random_range(0.2, 0.3);
+        let axis = Vec3::random_in_unit_sphere().normalized();
+        let angle = rand::random_range(0.0, std::f32::consts::TAU);
+
+        leaves.add(
+            Rectangle::new_xy(
+                -half_size,
+                half_size,
+                -half_size,
+                half_size,
+                0.0,
+                leaf.clone(),
+            )
+            .rotate(axis, angle)
+            .translate(center),
+        );
+    }
+    objects.add(BvhNode::from_list(&leaves, time));
+
+    let sun = DiffuseLight::from_color(Color::new(10.0, 9.0, 8.0));
+    objects.add(Sphere::new(Point3::new(20.0, 30.0, 20.0), 8.0, sun));
+
+    Ok(objects)
+}
@@ -0,0 +1,44 @@
+use crate::{
+    color::Color,
+    materials::{Dielectric, Lambertian},
+    objects::{Cuboid, Object, ObjectList, Sphere},
+    textures::Checker,
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    // Glass spheres from polished to heavily frosted
+    for (i, roughness) in [0.0, 0.15, 0.4].iter().enumerate() {
+        let z = 2.2 - 2.2 * i as f32;
+        let glass = Dielectric::new(1.5).with_roughness(*roughness);
+        objects.add(Sphere::new(Point3::new(0.0, 1.0, z), 1.0, glass));
+    }
+
+    // Ground glass pane in front of colored spheres
+    let pane = Dielectric::new(1.5).with_roughness(0.3);
+    objects.add(Cuboid::bounded_by(
+        Point3::new(-2.0, 0.0, -5.5),
+        Point3::new(-1.9, 2.0, -3.5),
+        pane,
+    ));
+    let colors = [Color::new(0.8, 0.1, 0.1), Color::new(0.1, 0.2, 0.8)];
+    for (i, color) in colors.iter().enumerate() {
+        let z = -4.1 - 0.9 * i as f32;
+        objects.add(Sphere::new(
+            Point3::new(-4.0, 0.5, z),
+            0.5,
+            Lambertian::new(*color),
+        ));
+    }
+
+    objects
+}
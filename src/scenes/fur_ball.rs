@@ -0,0 +1,87 @@
+use std::f32::consts::PI;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    color::Color,
+    materials::{Hair, Lambertian},
+    objects::{BvhNode, Curve, CurveType, Object, ObjectList, Sphere},
+    textures::Checker,
+    time::Time,
+    vec3::{Point3, Vec3},
+};
+
+pub fn new(time: Time) -> impl Object {
+    let mut world = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    world.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    let mut rng = ChaCha8Rng::seed_from_u64(123);
+    let mut random_direction = || {
+        let z = rng.gen_range(-1.0f32..1.0);
+        let phi = rng.gen_range(0.0..2.0 * PI);
+        let r = (1.0 - z * z).sqrt();
+        Vec3::new(r * phi.cos(), r * phi.sin(), z)
+    };
+
+    // Ball covered in fur
+    let center = Point3::new(0.0, 1.0, 0.0);
+    world.add(Sphere::new(
+        center,
+        0.8,
+        Lambertian::new(Color::new(0.3, 0.15, 0.05)),
+    ));
+
+    let fur = Hair::new(Color::new(0.6, 0.35, 0.15), 0.2, 0.1);
+    let gravity = Vec3::new(0.0, -0.15, 0.0);
+    let mut strands = ObjectList::new(vec![]);
+    for _ in 0..4000 {
+        let direction = random_direction();
+        let root = center + 0.8 * direction;
+        let p1 = root + 0.2 * direction;
+        let p2 = p1 + 0.15 * (direction + 0.3 * random_direction()) + gravity;
+        let p3 = p2 + 0.1 * (direction + 0.3 * random_direction()) + 2.0 * gravity;
+
+        let strand = Curve::new(
+            [root, p1, p2, p3],
+            (0.012, 0.002),
+            CurveType::Cylinder,
+            fur.clone(),
+        );
+        for segment in strand.split(3) {
+            strands.add(segment);
+        }
+    }
+    world.add(BvhNode::from_list(&strands, time));
+
+    // Tuft of grass blades next to it
+    let grass = Lambertian::new(Color::new(0.2, 0.5, 0.1));
+    let mut blades = ObjectList::new(vec![]);
+    for _ in 0..400 {
+        let root = Point3::new(rng.gen_range(-0.5..0.5), 0.0, rng.gen_range(1.5..3.0));
+        let lean = Vec3::new(rng.gen_range(-0.2..0.2), 0.0, rng.gen_range(-0.2..0.2));
+        let height = rng.gen_range(0.3..0.7);
+        let up = Vec3::new(0.0, height / 3.0, 0.0);
+
+        blades.add(Curve::new(
+            [
+                root,
+                root + up,
+                root + 2.0 * up + lean,
+                root + 3.0 * up + 2.5 * lean,
+            ],
+            (0.04, 0.0),
+            CurveType::Ribbon,
+            grass.clone(),
+        ));
+    }
+    world.add(BvhNode::from_list(&blades, time));
+
+    world
+}
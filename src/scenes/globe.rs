@@ -1,14 +1,14 @@
 use crate::{
     materials::Lambertian,
     objects::{Object, Sphere},
-    textures,
+    textures::{ImageTexture, TextureError},
     vec3::Point3,
 };
 
-pub fn new() -> impl Object {
-    let earth_texture = textures::ImageTexture::new("assets/earthmap.jpg");
+pub fn new() -> Result<impl Object, TextureError> {
+    let earth_texture = ImageTexture::new("assets/earthmap.jpg")?;
     let earth_surface = Lambertian::from_texture(earth_texture);
     let globe = Sphere::new(Point3::new(0.0, 0.0, 0.0), 2.0, earth_surface);
 
-    globe
+    Ok(globe)
 }
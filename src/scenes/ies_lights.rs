@@ -0,0 +1,52 @@
+use crate::{
+    color::Color,
+    ies::IesProfile,
+    lights::{AreaLight, Light},
+    materials::{DiffuseLight, Lambertian, LightPower},
+    objects::{Object, ObjectList, Rectangle, Sphere},
+    scenes::SceneError,
+    vec3::{Point3, Vec3},
+};
+
+const PROFILE: &str = "assets/downlight.ies";
+
+/// Wall and floor lit by downlights, whose photometric profile draws the typical scallops
+/// onto the wall, and a ceiling panel shaped by the same profile.
+pub fn new(lights: &mut Vec<Light>) -> Result<impl Object, SceneError> {
+    let mut objects = ObjectList::new(vec![]);
+
+    let white = Lambertian::new(Color::new(0.75, 0.75, 0.75));
+    objects.add(Rectangle::new_xz(
+        -1.0,
+        10.0,
+        -6.0,
+        6.0,
+        0.0,
+        Lambertian::new(Color::new(0.4, 0.35, 0.3)),
+    ));
+    objects.add(Rectangle::new_yz(0.0, 4.0, -6.0, 6.0, 0.0, white.clone()));
+
+    objects.add(Sphere::new(Point3::new(1.5, 0.6, 1.5), 0.6, white));
+
+    let profile = IesProfile::load(PROFILE)?;
+    lights.extend([-3.0, 0.0, 3.0].into_iter().map(|z| {
+        // The downlights are as bright as the candela in the file
+        Light::point(Point3::new(0.5, 3.8, z), Color::new(1.0, 0.92, 0.83))
+            .with_candela(profile.clone())
+    }));
+
+    // The panel faces down, so its nadir points at the floor
+    let panel = AreaLight::new(
+        Point3::new(3.0, 3.9, -1.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 2.0),
+        DiffuseLight::from_color(Color::new(1.0, 0.95, 0.9))
+            .one_sided()
+            .with_profile(profile),
+    )?
+    .with_power(LightPower::Watts(6.0))?;
+    objects.add(panel.object());
+    lights.push(panel.into());
+
+    Ok(objects)
+}
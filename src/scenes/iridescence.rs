@@ -0,0 +1,55 @@
+use crate::{
+    color::Color,
+    materials::{Conductor, Dielectric, Lambertian, ThinFilm},
+    objects::{Object, ObjectList, Sphere},
+    textures::{Checker, NoiseTexture},
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    // Soap bubble, a film of soapy water with air on both sides
+    let soap = ThinFilm::from_texture(NoiseTexture::new(3.0), 200.0, 900.0, 1.33);
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -3.3),
+        1.0,
+        Dielectric::new(1.0).with_thin_film(soap),
+    ));
+
+    // Lens glass with an anti-reflective coating of magnesium fluoride
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -1.1),
+        1.0,
+        Dielectric::new(1.5).with_thin_film(ThinFilm::new(100.0, 1.38)),
+    ));
+
+    // Heat-tinted titanium, with an oxide layer that grows thicker towards the hot spots
+    let oxide = ThinFilm::from_texture(NoiseTexture::new(1.5), 50.0, 250.0, 2.4);
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 1.1),
+        1.0,
+        Conductor::new(
+            Color::new(2.74, 2.54, 2.27),
+            Color::new(3.81, 3.43, 3.04),
+            0.15,
+        )
+        .with_thin_film(oxide),
+    ));
+
+    // Polished gold under a uniform film
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 3.3),
+        1.0,
+        Conductor::gold(0.05).with_thin_film(ThinFilm::new(400.0, 1.5)),
+    ));
+
+    objects
+}
@@ -0,0 +1,40 @@
+use crate::{
+    color::Color,
+    materials::{Conductor, Lambertian},
+    objects::{Object, ObjectList, Sphere},
+    textures::Checker,
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    // Front row is polished, back row rough
+    let metals: [fn(f32) -> Conductor<'static>; 4] = [
+        Conductor::gold,
+        Conductor::copper,
+        Conductor::aluminium,
+        Conductor::silver,
+    ];
+    for (i, metal) in metals.iter().enumerate() {
+        let z = 3.3 - 2.2 * i as f32;
+        objects.add(Sphere::new(Point3::new(1.2, 1.0, z), 1.0, metal(0.05)));
+        objects.add(Sphere::new(Point3::new(-1.2, 1.0, z), 1.0, metal(0.4)));
+    }
+
+    // Brushed gold, rough along the latitude lines of the sphere
+    objects.add(Sphere::new(
+        Point3::new(-3.6, 1.0, 0.0),
+        1.0,
+        Conductor::gold(0.0).with_roughness(0.6, 0.05),
+    ));
+
+    objects
+}
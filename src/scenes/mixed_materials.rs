@@ -0,0 +1,38 @@
+use crate::{
+    color::Color,
+    materials::{Conductor, DiffuseLight, Lambertian, Metal, MixMaterial},
+    objects::{Object, ObjectList, Sphere},
+    textures::{Checker, NoiseTexture},
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    // Floor alternating between polished and matte tiles
+    let tiles = Checker::from_colors(Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0));
+    let floor = MixMaterial::new(
+        Lambertian::new(Color::new(0.7, 0.7, 0.7)),
+        Metal::new(Color::new(0.8, 0.8, 0.8), 0.02),
+        tiles,
+    );
+    objects.add(Sphere::new(Point3::new(0.0, -1000.0, 0.0), 1000.0, floor));
+
+    // Copper patchily covered in rust
+    let rust = MixMaterial::new(
+        Conductor::copper(0.2),
+        Lambertian::new(Color::new(0.45, 0.2, 0.08)),
+        NoiseTexture::new(4.0),
+    );
+    objects.add(Sphere::new(Point3::new(0.0, 1.0, -1.2), 1.0, rust));
+
+    // Glowing veins in dark stone, where emission mixes along with the surface
+    let veins = MixMaterial::new(
+        Lambertian::new(Color::new(0.1, 0.1, 0.1)),
+        DiffuseLight::from_color(Color::new(4.0, 1.5, 0.5)),
+        Checker::from_colors(Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)),
+    );
+    objects.add(Sphere::new(Point3::new(0.0, 1.0, 1.2), 1.0, veins));
+
+    objects
+}
@@ -1,19 +1,79 @@
 mod book_cover;
+mod bump_mapping;
+mod coated_spheres;
 mod cornell_box;
 mod cornell_smoke;
+mod csg_shapes;
+mod daylight;
+mod delta_lights;
+mod dispersion;
+mod emissive_textures;
+mod environment_lighting;
 mod final_scene;
+mod foliage;
+mod frosted_glass;
+mod fur_ball;
 mod globe;
+mod ies_lights;
+mod iridescence;
+mod metals;
+mod mixed_materials;
 mod perlin_spheres;
+mod principled_spheres;
+mod rough_diffuse;
+mod sdf_shapes;
 mod simple_light;
+mod subsurface_spheres;
+mod terrain;
+mod texture_filtering;
 mod three_spheres;
+mod tinted_glass;
 mod two_spheres;
 
 use clap::ValueEnum;
+use thiserror::Error;
 
-use crate::{color::Color, objects::BoxedObject, ray::Ray, settings::SceneSettings, time::Time};
+use crate::{
+    color::Color,
+    environment::{EnvironmentError, EnvironmentMap},
+    ies::IesError,
+    lights::{AreaLightError, Light},
+    medium::{Interaction, Medium},
+    objects::{BoxedObject, HeightfieldError, HitRecord},
+    ray::Ray,
+    settings::SceneSettings,
+    sky::Sky,
+    spectrum::{PathSampling, SampledSpectrum},
+    textures::{TextureCache, TextureError},
+    time::Time,
+    vec3::Vec3,
+};
+
+/// Upper bound on the number of times a path scatters inside media, which don't count
+/// towards the maximum depth since random walks inside dense media take many steps.
+const MAX_SCATTERING_EVENTS: usize = 256;
+
+#[derive(Error, Debug)]
+pub enum SceneError {
+    #[error(transparent)]
+    Environment(#[from] EnvironmentError),
+    #[error(transparent)]
+    Ies(#[from] IesError),
+    #[error(transparent)]
+    Texture(#[from] TextureError),
+    #[error(transparent)]
+    AreaLight(#[from] AreaLightError),
+    #[error(transparent)]
+    Heightfield(#[from] HeightfieldError),
+}
 
 pub struct Scene<'a> {
     objects: BoxedObject<'a>,
+    /// Lights sampled explicitly at every hit, which includes the point, spot and
+    /// directional lights that rays can never hit.
+    lights: Vec<Light>,
+    /// Light arriving from all directions, which takes the place of the background.
+    environment: Option<EnvironmentMap>,
     background: Color,
 }
 
@@ -28,60 +88,315 @@ pub enum SceneType {
     CornellBox,
     CornellSmoke,
     FinalScene,
+    CsgShapes,
+    SdfShapes,
+    Terrain,
+    FurBall,
+    BumpMapping,
+    Foliage,
+    Metals,
+    FrostedGlass,
+    TintedGlass,
+    Dispersion,
+    PrincipledSpheres,
+    MixedMaterials,
+    CoatedSpheres,
+    RoughDiffuse,
+    Iridescence,
+    SubsurfaceSpheres,
+    DeltaLights,
+    EnvironmentLighting,
+    Daylight,
+    IesLights,
+    EmissiveTextures,
+    TextureFiltering,
 }
 
 impl Scene<'_> {
-    pub fn from_settings(settings: SceneSettings) -> Self {
-        Self::new(settings.scene_type, settings.time, settings.background)
+    pub fn from_settings(settings: SceneSettings) -> Result<Self, SceneError> {
+        let mut scene = Self::new(settings.scene_type, settings.time, settings.background)?;
+        if let Some(environment) = settings.environment {
+            let map = EnvironmentMap::load(&environment.path)?
+                .with_rotation(environment.rotation)
+                .with_intensity(environment.intensity);
+            scene.environment = Some(map);
+        } else if let Some(sky) = settings.sky {
+            let sky = Sky::from_settings(sky);
+            scene.environment = Some(sky.environment_map());
+            scene.lights.push(sky.sun());
+        }
+
+        Ok(scene)
     }
 
-    pub fn new(scene_type: SceneType, time: Time, background: Color) -> Self {
+    pub fn new(scene_type: SceneType, time: Time, background: Color) -> Result<Self, SceneError> {
+        let mut textures = TextureCache::new();
+        let mut lights = Vec::new();
         let objects: BoxedObject = match scene_type {
             SceneType::TwoSpheres => Box::new(two_spheres::new()),
             SceneType::ThreeSpheres => Box::new(three_spheres::new(time)),
             SceneType::BookCover => Box::new(book_cover::new(time)),
             SceneType::PerlinSpheres => Box::new(perlin_spheres::new()),
-            SceneType::Globe => Box::new(globe::new()),
-            SceneType::SimpleLight => Box::new(simple_light::new()),
-            SceneType::CornellBox => Box::new(cornell_box::new()),
-            SceneType::CornellSmoke => Box::new(cornell_smoke::new()),
-            SceneType::FinalScene => Box::new(final_scene::new(time)),
+            SceneType::Globe => Box::new(globe::new()?),
+            SceneType::SimpleLight => Box::new(simple_light::new()?),
+            SceneType::CornellBox => Box::new(cornell_box::new(&mut lights)?),
+            SceneType::CornellSmoke => Box::new(cornell_smoke::new(&mut lights)?),
+            SceneType::FinalScene => Box::new(final_scene::new(time)?),
+            SceneType::CsgShapes => Box::new(csg_shapes::new()),
+            SceneType::SdfShapes => Box::new(sdf_shapes::new()),
+            SceneType::Terrain => Box::new(terrain::new()?),
+            SceneType::FurBall => Box::new(fur_ball::new(time)),
+            SceneType::BumpMapping => Box::new(bump_mapping::new()?),
+            SceneType::Foliage => Box::new(foliage::new(time)?),
+            SceneType::Metals => Box::new(metals::new()),
+            SceneType::FrostedGlass => Box::new(frosted_glass::new()),
+            SceneType::TintedGlass => Box::new(tinted_glass::new()),
+            SceneType::Dispersion => Box::new(dispersion::new()),
+            SceneType::PrincipledSpheres => Box::new(principled_spheres::new()),
+            SceneType::MixedMaterials => Box::new(mixed_materials::new()),
+            SceneType::CoatedSpheres => Box::new(coated_spheres::new()),
+            SceneType::RoughDiffuse => Box::new(rough_diffuse::new()),
+            SceneType::Iridescence => Box::new(iridescence::new()),
+            SceneType::SubsurfaceSpheres => Box::new(subsurface_spheres::new()),
+            SceneType::DeltaLights => Box::new(delta_lights::new(&mut lights)),
+            SceneType::EnvironmentLighting => Box::new(environment_lighting::new()),
+            SceneType::Daylight => Box::new(daylight::new()),
+            SceneType::IesLights => Box::new(ies_lights::new(&mut lights)?),
+            SceneType::EmissiveTextures => {
+                Box::new(emissive_textures::new(&mut textures, &mut lights)?)
+            }
+            SceneType::TextureFiltering => Box::new(texture_filtering::new(&mut textures)?),
         };
 
-        Self {
+        Ok(Self {
             objects,
+            lights,
+            environment: None,
             background,
-        }
+        })
     }
 
     pub fn ray_color(&self, ray: &Ray, max_depth: usize) -> Color {
-        let mut color = Color::new(1.0, 1.0, 1.0);
-        let mut ray = ray.clone();
-        for _ in 0..max_depth {
+        self.trace(ray, max_depth, PathSampling::Rgb)
+    }
+
+    /// Traces the ray with a handful of randomly sampled wavelengths instead of RGB,
+    /// which is needed for dispersion.
+    pub fn ray_color_spectral(&self, ray: &Ray, max_depth: usize) -> Color {
+        self.trace(ray, max_depth, PathSampling::spectral())
+    }
+
+    fn trace(&self, ray: &Ray, max_depth: usize, mut sampling: PathSampling) -> Color {
+        let mut radiance = SampledSpectrum::constant(0.0);
+        let mut throughput = SampledSpectrum::constant(1.0);
+        let mut ray = ray.with_wavelength(sampling.wavelength());
+        // Media the ray is inside of, innermost last
+        let mut media: Vec<Medium> = Vec::new();
+        let mut bounces = 0;
+        let mut scattering_events = 0;
+        // Density with which a material sampled the direction of the ray
+        let mut scatter_pdf = 0.0;
+        while bounces < max_depth {
             let hr = match self.objects.hit(&ray, 0.001, f32::INFINITY) {
                 Some(hr) => hr,
                 None => {
-                    // Background
-                    color *= self.background;
+                    radiance += throughput * sampling.upsample(self.miss(&ray, scatter_pdf));
                     break;
                 }
             };
 
-            let emitted = hr.material.emitted(hr.u, hr.v, &hr.point);
+            if let Some(medium) = media.last() {
+                let ray_length = ray.direction().length();
+                match medium.sample_interaction(hr.t * ray_length) {
+                    Interaction::Pass { weight } => throughput *= sampling.upsample(weight),
+                    Interaction::Scatter { distance, weight } => {
+                        scattering_events += 1;
+                        if scattering_events > MAX_SCATTERING_EVENTS {
+                            break;
+                        }
 
-            match hr.material.scatter(&ray, &hr) {
-                Some(scatter) => {
-                    color = emitted + color * scatter.attenuation;
-                    ray = scatter.scattered;
+                        // Isotropic phase function, like `ConstantMedium`
+                        throughput *= sampling.upsample(weight);
+                        let point = ray.at(distance / ray_length);
+                        ray = Ray::new_time_based(point, Vec3::random_in_unit_sphere(), ray.time())
+                            .with_wavelength(sampling.wavelength())
+                            .with_cone(ray.footprint(distance / ray_length), ray.cone_spread());
+                        scatter_pdf = 0.0;
+                        continue;
+                    }
                 }
-                None => {
-                    // Hit a light source or got absorbed
-                    color *= emitted;
-                    break;
+            }
+
+            let emitted = hr.material.emitted(&ray, &hr);
+            if emitted != Color::new(0.0, 0.0, 0.0) {
+                let weight = self.emission_weight(&ray, &hr, scatter_pdf);
+                radiance += throughput * sampling.upsample(emitted * weight);
+            }
+            radiance += throughput * sampling.upsample(self.sample_lights(&ray, &hr));
+
+            // Hit a light source or got absorbed
+            let Some(scatter) = hr.material.scatter(&ray, &hr) else {
+                break;
+            };
+
+            // Transmitting through the surface enters or leaves the object's medium.
+            // Overlapping objects are assumed to be nested.
+            let transmitted = scatter.scattered.direction().dot(hr.normal) < 0.0;
+            if let (true, Some(medium)) = (transmitted, hr.material.medium()) {
+                if hr.front_face {
+                    media.push(medium);
+                } else {
+                    media.pop();
                 }
             }
+
+            if hr.material.is_dispersive() {
+                sampling.terminate_secondary();
+            }
+
+            throughput *= sampling.upsample(scatter.attenuation);
+            // The cone keeps widening from the footprint on the surface. Curvature and
+            // roughness would widen it further, which is left out.
+            ray = scatter
+                .scattered
+                .with_wavelength(sampling.wavelength())
+                .with_cone(ray.footprint(hr.t), ray.cone_spread());
+            scatter_pdf = scatter.pdf;
+            bounces += 1;
+        }
+
+        sampling.resolve(radiance)
+    }
+
+    /// Light arriving along a ray that left the scene. Rays scattered with the density
+    /// `scatter_pdf` are weighted against `sample_lights` picking the same direction.
+    fn miss(&self, ray: &Ray, scatter_pdf: f32) -> Color {
+        let count = self.light_count() as f32;
+        let weight = |light_pdf: f32| {
+            if scatter_pdf > 0.0 {
+                power_heuristic(scatter_pdf, light_pdf / count)
+            } else {
+                1.0
+            }
+        };
+
+        // The sun disc of directional lights
+        let direction = ray.direction();
+        let lights = self
+            .lights
+            .iter()
+            .filter_map(|light| light.radiance(direction))
+            .fold(Color::new(0.0, 0.0, 0.0), |sum, (radiance, pdf)| {
+                sum + radiance * weight(pdf)
+            });
+
+        match &self.environment {
+            Some(environment) => {
+                lights + environment.radiance(direction) * weight(environment.pdf(direction))
+            }
+            None => lights + self.background,
+        }
+    }
+
+    /// Weight of light emitted by a surface that a ray scattered with the density
+    /// `scatter_pdf` hit, against `sample_lights` picking the same point on an area light.
+    fn emission_weight(&self, ray: &Ray, hit_record: &HitRecord, scatter_pdf: f32) -> f32 {
+        if scatter_pdf <= 0.0 {
+            return 1.0;
+        }
+
+        let light_pdf: f32 = self
+            .lights
+            .iter()
+            .map(|light| light.pdf(*ray.origin(), hit_record.point))
+            .sum();
+        if light_pdf <= 0.0 {
+            return 1.0;
+        }
+        power_heuristic(scatter_pdf, light_pdf / self.light_count() as f32)
+    }
+
+    /// Number of lights `sample_lights` picks from, counting the environment as one.
+    fn light_count(&self) -> usize {
+        self.lights.len() + usize::from(self.environment.is_some())
+    }
+
+    /// Estimates the light arriving at the hit directly from the lights and reflected along
+    /// the ray, by sampling one of the lights and tracing a shadow ray towards it.
+    fn sample_lights(&self, ray: &Ray, hit_record: &HitRecord) -> Color {
+        let black = Color::new(0.0, 0.0, 0.0);
+        let count = self.light_count();
+        if count == 0 {
+            return black;
+        }
+
+        let index = ((rand::random::<f32>() * count as f32) as usize).min(count - 1);
+        if index == self.lights.len() {
+            return self.sample_environment(ray, hit_record, count);
+        }
+
+        let Some(sample) = self.lights[index].sample(hit_record.point) else {
+            return black;
+        };
+
+        let reflectance = hit_record.material.eval(ray, hit_record, sample.direction);
+        if reflectance == black {
+            return black;
+        }
+
+        let shadow_ray = Ray::new_time_based(hit_record.point, sample.direction, ray.time());
+        if self
+            .objects
+            .hit(&shadow_ray, 0.001, sample.distance)
+            .is_some()
+        {
+            return black;
         }
 
-        color
+        // Lights that rays can also find by scattering are weighted against that
+        let weight = if sample.pdf > 0.0 {
+            let scatter_pdf = hit_record.material.pdf(ray, hit_record, sample.direction);
+            power_heuristic(sample.pdf / count as f32, scatter_pdf)
+        } else {
+            1.0
+        };
+        reflectance * sample.radiance * (count as f32 * weight)
+    }
+    /// Estimates the light arriving at the hit from the environment, which is picked out of
+    /// `count` lights. Directions are weighted against the material scattering towards them,
+    /// which finds reflections in shiny surfaces more reliably.
+    fn sample_environment(&self, ray: &Ray, hit_record: &HitRecord, count: usize) -> Color {
+        let black = Color::new(0.0, 0.0, 0.0);
+        let Some(sample) = self.environment.as_ref().and_then(EnvironmentMap::sample) else {
+            return black;
+        };
+
+        let reflectance = hit_record.material.eval(ray, hit_record, sample.direction);
+        if reflectance == black {
+            return black;
+        }
+
+        let shadow_ray = Ray::new_time_based(hit_record.point, sample.direction, ray.time());
+        if self
+            .objects
+            .hit(&shadow_ray, 0.001, f32::INFINITY)
+            .is_some()
+        {
+            return black;
+        }
+
+        let light_pdf = sample.pdf / count as f32;
+        let scatter_pdf = hit_record.material.pdf(ray, hit_record, sample.direction);
+        reflectance * sample.radiance * (power_heuristic(light_pdf, scatter_pdf) / light_pdf)
+    }
+}
+
+/// Weight of a sample drawn with the density `pdf` when another strategy could have drawn it
+/// with the density `other_pdf`, which favors whichever strategy samples it better.
+fn power_heuristic(pdf: f32, other_pdf: f32) -> f32 {
+    let (a, b) = (pdf * pdf, other_pdf * other_pdf);
+    if a + b <= 0.0 {
+        return 0.0;
     }
+    a / (a + b)
 }
@@ -0,0 +1,65 @@
+use crate::{
+    color::Color,
+    materials::{Lambertian, Principled},
+    objects::{Object, ObjectList, Sphere},
+    textures::{Checker, NoiseTexture},
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    let red = Color::new(0.8, 0.1, 0.1);
+    let gold = Color::new(1.0, 0.75, 0.35);
+    let materials = [
+        // Back row: plastic from glossy to rough
+        Principled::new(red).with_roughness(0.1),
+        Principled::new(red).with_roughness(0.4),
+        Principled::new(red).with_roughness(0.9),
+        // Middle row: metals, one with roughness driven by a texture
+        Principled::new(gold).with_metallic(1.0).with_roughness(0.2),
+        Principled::new(gold)
+            .with_metallic(1.0)
+            .with_roughness(NoiseTexture::new(4.0)),
+        Principled::new(gold).with_metallic(0.5).with_roughness(0.3),
+        // Front row: car paint, velvet, frosted glass and wax
+        Principled::new(Color::new(0.1, 0.2, 0.6))
+            .with_roughness(0.6)
+            .with_specular(1.0)
+            .with_clearcoat(1.0, 0.05),
+        Principled::new(Color::new(0.4, 0.05, 0.2))
+            .with_roughness(1.0)
+            .with_sheen(1.0),
+        Principled::new(Color::new(0.9, 1.0, 0.9))
+            .with_roughness(0.1)
+            .with_transmission(1.0),
+        Principled::new(Color::new(0.9, 0.8, 0.6))
+            .with_roughness(0.5)
+            .with_subsurface(0.6),
+    ];
+
+    let positions = [
+        (-2.0, -2.4),
+        (-2.0, 0.0),
+        (-2.0, 2.4),
+        (0.0, -2.4),
+        (0.0, 0.0),
+        (0.0, 2.4),
+        (2.0, -3.3),
+        (2.0, -1.1),
+        (2.0, 1.1),
+        (2.0, 3.3),
+    ];
+    for (material, (x, z)) in materials.into_iter().zip(positions) {
+        objects.add(Sphere::new(Point3::new(x, 0.9, z), 0.9, material));
+    }
+
+    objects
+}
@@ -0,0 +1,51 @@
+use crate::{
+    color::Color,
+    materials::{DiffuseLight, Lambertian, OrenNayar},
+    objects::{Object, ObjectList, Sphere},
+    textures::NoiseTexture,
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        OrenNayar::new(Color::new(0.5, 0.5, 0.5)),
+    ));
+
+    // Clay of increasing roughness, starting from a smooth Lambertian for comparison
+    let clay = Color::new(0.7, 0.4, 0.3);
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -3.3),
+        1.0,
+        Lambertian::new(clay),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -1.1),
+        1.0,
+        OrenNayar::new(clay).with_roughness(0.5),
+    ));
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 1.1),
+        1.0,
+        OrenNayar::new(clay).with_roughness(1.0),
+    ));
+
+    // Roughness driven by a texture
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 3.3),
+        1.0,
+        OrenNayar::new(Color::new(0.8, 0.8, 0.75)).with_roughness(NoiseTexture::new(4.0)),
+    ));
+
+    // Light from behind the camera, where rough surfaces look flat
+    objects.add(Sphere::new(
+        Point3::new(40.0, 20.0, 0.0),
+        12.0,
+        DiffuseLight::from_color(Color::new(3.0, 3.0, 3.0)),
+    ));
+
+    objects
+}
@@ -0,0 +1,102 @@
+use crate::{
+    aabb::AABB,
+    color::Color,
+    materials::{Dielectric, Lambertian, Metal},
+    objects::{Object, ObjectList, SdfObject, Sphere},
+    sdf::{
+        BoxSdf, CapsuleSdf, Difference, Intersection, Repetition, SmoothUnion, SphereSdf, TorusSdf,
+        Translation, Twist, Union,
+    },
+    textures::Checker,
+    vec3::{Point3, Vec3},
+};
+
+pub fn new() -> impl Object {
+    let mut world = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    world.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    // Sphere melting into a ring
+    let blob = SmoothUnion::new(
+        SphereSdf::new(Point3::new(0.0, 1.0, 0.0), 0.7),
+        TorusSdf::new(Point3::new(0.0, 0.6, 0.0), 1.0, 0.25),
+        0.4,
+    );
+    world.add(SdfObject::new(
+        blob,
+        AABB::new(Point3::new(-1.3, 0.3, -1.3), Point3::new(1.3, 1.8, 1.3)),
+        Lambertian::new(Color::new(0.8, 0.3, 0.2)),
+    ));
+
+    // Twisted column
+    let column = Translation::new(
+        Twist::new(
+            BoxSdf::rounded(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.4, 1.0, 0.4), 0.05),
+            1.2,
+        ),
+        Vec3::new(0.0, 0.0, -2.8),
+    );
+    world.add(
+        SdfObject::new(
+            column,
+            AABB::new(Point3::new(-0.6, 0.0, -3.4), Point3::new(0.6, 2.0, -2.2)),
+            Metal::new(Color::new(0.8, 0.7, 0.5), 0.05),
+        )
+        .with_step_scale(0.5),
+    );
+
+    // Cube made out of a grid of spheres
+    let grid = Intersection::new(
+        Repetition::new(
+            SphereSdf::new(Point3::new(0.0, 0.0, 0.0), 0.15),
+            Vec3::new(0.4, 0.4, 0.4),
+        ),
+        BoxSdf::new(Point3::new(0.0, 0.6, 2.8), Vec3::new(0.6, 0.6, 0.6)),
+    );
+    world.add(SdfObject::new(
+        grid,
+        AABB::new(Point3::new(-0.6, 0.0, 2.2), Point3::new(0.6, 1.2, 3.4)),
+        Lambertian::new(Color::new(0.2, 0.4, 0.8)),
+    ));
+
+    // Hollowed out glass box with a handle
+    let hollow = Union::new(
+        Difference::new(
+            BoxSdf::rounded(Point3::new(2.5, 0.4, 1.2), Vec3::new(0.4, 0.4, 0.4), 0.1),
+            SphereSdf::new(Point3::new(2.5, 0.8, 1.2), 0.45),
+        ),
+        CapsuleSdf::new(Point3::new(2.5, 0.4, 1.7), Point3::new(2.5, 0.4, 2.2), 0.1),
+    );
+    world.add(SdfObject::new(
+        hollow,
+        AABB::new(Point3::new(2.0, 0.0, 0.7), Point3::new(3.0, 0.9, 2.4)),
+        Dielectric::new(1.5),
+    ));
+
+    // Any closure can be used as a distance function, here a (non-exact) ellipsoid
+    let center = Point3::new(2.5, 0.5, -1.3);
+    let radii = Vec3::new(0.3, 0.5, 0.6);
+    let ellipsoid = move |point: &Point3| {
+        let p = point - center;
+        let k0 = Vec3::new(p.x() / radii.x(), p.y() / radii.y(), p.z() / radii.z()).length();
+        let k1 = Vec3::new(
+            p.x() / (radii.x() * radii.x()),
+            p.y() / (radii.y() * radii.y()),
+            p.z() / (radii.z() * radii.z()),
+        )
+        .length();
+        k0 * (k0 - 1.0) / k1
+    };
+    world.add(SdfObject::new(
+        ellipsoid,
+        AABB::new(center - radii, center + radii),
+        Lambertian::new(Color::new(0.9, 0.8, 0.2)),
+    ));
+
+    world
+}
@@ -2,11 +2,11 @@ use crate::{
     color::Color,
     materials::{Dielectric, DiffuseLight, Lambertian},
     objects::{Object, ObjectList, Rectangle, Sphere},
-    textures::{ImageTexture, NoiseTexture},
+    textures::{ImageTexture, NoiseTexture, TextureError},
     vec3::Point3,
 };
 
-pub fn new() -> impl Object {
+pub fn new() -> Result<impl Object, TextureError> {
     let mut objects = ObjectList::new(vec![]);
 
     let perlin_texture = NoiseTexture::new(4.);
@@ -24,7 +24,7 @@ pub fn new() -> impl Object {
     objects.add(Sphere::new(
         Point3::new(-4.5, 2.0, 0.0),
         2.0,
-        Lambertian::from_texture(ImageTexture::new("assets/earthmap.jpg")),
+        Lambertian::from_texture(ImageTexture::new("assets/earthmap.jpg")?),
     ));
     objects.add(Rectangle::new_yz(
         3.0,
@@ -38,5 +38,5 @@ pub fn new() -> impl Object {
     let light_texture = DiffuseLight::from_color(Color::new(4.0, 4.0, 4.0));
     objects.add(Sphere::new(Point3::new(-2.0, 8.0, 3.0), 2.0, light_texture));
 
-    objects
+    Ok(objects)
 }
@@ -0,0 +1,56 @@
+use crate::{
+    color::Color,
+    materials::{DiffuseLight, Lambertian, Subsurface},
+    objects::{Object, ObjectList, Sphere},
+    textures::Checker,
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    // Lambertian for comparison
+    let skin_color = Color::new(0.8, 0.55, 0.45);
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -3.3),
+        1.0,
+        Lambertian::new(skin_color),
+    ));
+
+    // Skin, where red light travels furthest
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -1.1),
+        1.0,
+        Subsurface::new(skin_color, Color::new(0.4, 0.15, 0.08)),
+    ));
+
+    // Marble
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 1.1),
+        1.0,
+        Subsurface::new(Color::new(0.9, 0.9, 0.88), Color::new(0.1, 0.1, 0.1)),
+    ));
+
+    // Candle wax, with a rough surface
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, 3.3),
+        1.0,
+        Subsurface::new(Color::new(0.95, 0.85, 0.6), Color::new(0.5, 0.4, 0.2)).with_roughness(0.4),
+    ));
+
+    // Light behind the spheres, which shines through their thin edges
+    objects.add(Sphere::new(
+        Point3::new(-20.0, 8.0, 0.0),
+        6.0,
+        DiffuseLight::from_color(Color::new(6.0, 6.0, 6.0)),
+    ));
+
+    objects
+}
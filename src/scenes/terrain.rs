@@ -0,0 +1,28 @@
+use crate::{
+    color::Color,
+    materials::{DiffuseLight, Lambertian},
+    objects::{Heightfield, Object, ObjectList, Sphere},
+    scenes::SceneError,
+    textures::ImageTexture,
+    vec3::{Point3, Vec3},
+};
+
+pub fn new() -> Result<impl Object, SceneError> {
+    let mut objects = ObjectList::new(vec![]);
+
+    // The heightmap doubles as the terrain's color, which lines up because the
+    // heightfield's UVs match the image layout.
+    let texture = ImageTexture::new("assets/heightmap.png")?;
+    let terrain = Heightfield::from_image(
+        "assets/heightmap.png",
+        Point3::new(-10.0, 0.0, -10.0),
+        Vec3::new(20.0, 4.0, 20.0),
+        Lambertian::from_texture(texture),
+    )?;
+    objects.add(terrain);
+
+    let sun = DiffuseLight::from_color(Color::new(8.0, 7.0, 6.0));
+    objects.add(Sphere::new(Point3::new(-20.0, 30.0, -20.0), 8.0, sun));
+
+    Ok(objects)
+}
@@ -0,0 +1,96 @@
+use crate::{
+    color::Color,
+    materials::{Lambertian, Principled},
+    objects::{Object, ObjectList, Rectangle, Sphere},
+    settings::{TextureFilter, TextureSettings, WrapMode},
+    textures::{TextureCache, TextureError},
+    vec3::Point3,
+};
+
+/// Tiled floor stretching to the horizon, where mipmapping keeps the tiles from breaking
+/// up into moiré, in front of panels showing the mirror wrap mode and magnified texels,
+/// and a metal sphere whose roughness comes from a floating point image.
+pub fn new(textures: &mut TextureCache) -> Result<impl Object, TextureError> {
+    let mut objects = ObjectList::new(vec![]);
+
+    let tiles = textures.load_with_settings(
+        "assets/tiles.png",
+        TextureSettings {
+            wrap: WrapMode::Repeat,
+            ..Default::default()
+        },
+    )?;
+    objects.add(
+        Rectangle::new_xz(
+            -100.0,
+            100.0,
+            -100.0,
+            100.0,
+            0.0,
+            Lambertian::from_texture(tiles),
+        )
+        .with_tiling(200.0, 200.0),
+    );
+
+    let mirrored = textures.load_with_settings(
+        "assets/earthmap.jpg",
+        TextureSettings {
+            wrap: WrapMode::Mirror,
+            ..Default::default()
+        },
+    )?;
+    objects.add(
+        Rectangle::new_xy(
+            -6.0,
+            -0.5,
+            0.5,
+            3.5,
+            -8.0,
+            Lambertian::from_texture(mirrored),
+        )
+        .with_tiling(2.0, 2.0),
+    );
+
+    // A few texels blown up to the size of the panel, once sharp and once smooth
+    let pixelated = textures.load_with_settings(
+        "assets/tiles.png",
+        TextureSettings {
+            filter: TextureFilter::Nearest,
+            ..Default::default()
+        },
+    )?;
+    objects.add(
+        Rectangle::new_xy(
+            0.5,
+            3.0,
+            0.5,
+            3.0,
+            -8.0,
+            Lambertian::from_texture(pixelated),
+        )
+        .with_tiling(0.6, 0.6),
+    );
+    let smooth = textures.load_with_settings(
+        "assets/tiles.png",
+        TextureSettings {
+            filter: TextureFilter::Bicubic,
+            ..Default::default()
+        },
+    )?;
+    objects.add(
+        Rectangle::new_xy(3.5, 6.0, 0.5, 3.0, -8.0, Lambertian::from_texture(smooth))
+            .with_tiling(0.6, 0.6),
+    );
+
+    // Roughness stored as floating point data, which is linear
+    let roughness = textures.load("assets/roughness.exr")?;
+    objects.add(Sphere::new(
+        Point3::new(0.0, 1.0, -3.0),
+        1.0,
+        Principled::new(Color::new(0.8, 0.8, 0.8))
+            .with_metallic(1.0)
+            .with_roughness(roughness),
+    ));
+
+    Ok(objects)
+}
@@ -0,0 +1,43 @@
+use crate::{
+    color::Color,
+    materials::{Dielectric, Lambertian},
+    objects::{Cuboid, Object, ObjectList, Sphere},
+    textures::Checker,
+    vec3::Point3,
+};
+
+pub fn new() -> impl Object {
+    let mut objects = ObjectList::new(vec![]);
+
+    let checker = Checker::from_colors(Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    objects.add(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Lambertian::from_texture(checker),
+    ));
+
+    // The same glass gets more saturated the thicker the object is
+    let glass = Dielectric::new(1.5).with_absorption(Color::new(0.2, 0.7, 0.8), 1.0);
+    for (i, radius) in [0.3, 0.6, 1.0].iter().enumerate() {
+        let z = 2.6 - 1.9 * i as f32;
+        objects.add(Sphere::new(
+            Point3::new(0.0, *radius, z),
+            *radius,
+            glass.clone(),
+        ));
+    }
+
+    // Thin pane next to a thick block
+    objects.add(Cuboid::bounded_by(
+        Point3::new(-0.05, 0.0, -2.6),
+        Point3::new(0.05, 1.5, -1.8),
+        glass.clone(),
+    ));
+    objects.add(Cuboid::bounded_by(
+        Point3::new(-0.6, 0.0, -4.2),
+        Point3::new(0.6, 1.5, -3.0),
+        glass,
+    ));
+
+    objects
+}
@@ -0,0 +1,195 @@
+use crate::vec3::{Point3, Vec3};
+
+use super::{BoxedSdf, Sdf};
+
+#[derive(Clone)]
+pub struct Union<'a> {
+    a: BoxedSdf<'a>,
+    b: BoxedSdf<'a>,
+}
+
+impl<'a> Union<'a> {
+    pub fn new(a: impl Sdf + Send + Sync + 'a, b: impl Sdf + Send + Sync + 'a) -> Self {
+        Self {
+            a: Box::new(a),
+            b: Box::new(b),
+        }
+    }
+}
+
+impl Sdf for Union<'_> {
+    fn distance(&self, point: &Point3) -> f32 {
+        self.a.distance(point).min(self.b.distance(point))
+    }
+}
+
+#[derive(Clone)]
+pub struct Intersection<'a> {
+    a: BoxedSdf<'a>,
+    b: BoxedSdf<'a>,
+}
+
+impl<'a> Intersection<'a> {
+    pub fn new(a: impl Sdf + Send + Sync + 'a, b: impl Sdf + Send + Sync + 'a) -> Self {
+        Self {
+            a: Box::new(a),
+            b: Box::new(b),
+        }
+    }
+}
+
+impl Sdf for Intersection<'_> {
+    fn distance(&self, point: &Point3) -> f32 {
+        self.a.distance(point).max(self.b.distance(point))
+    }
+}
+
+/// Everything inside `a` that is not inside `b`.
+#[derive(Clone)]
+pub struct Difference<'a> {
+    a: BoxedSdf<'a>,
+    b: BoxedSdf<'a>,
+}
+
+impl<'a> Difference<'a> {
+    pub fn new(a: impl Sdf + Send + Sync + 'a, b: impl Sdf + Send + Sync + 'a) -> Self {
+        Self {
+            a: Box::new(a),
+            b: Box::new(b),
+        }
+    }
+}
+
+impl Sdf for Difference<'_> {
+    fn distance(&self, point: &Point3) -> f32 {
+        self.a.distance(point).max(-self.b.distance(point))
+    }
+}
+
+/// Union that blends the two shapes together within a distance of `smoothness`.
+#[derive(Clone)]
+pub struct SmoothUnion<'a> {
+    a: BoxedSdf<'a>,
+    b: BoxedSdf<'a>,
+    smoothness: f32,
+}
+
+impl<'a> SmoothUnion<'a> {
+    pub fn new(
+        a: impl Sdf + Send + Sync + 'a,
+        b: impl Sdf + Send + Sync + 'a,
+        smoothness: f32,
+    ) -> Self {
+        Self {
+            a: Box::new(a),
+            b: Box::new(b),
+            smoothness,
+        }
+    }
+}
+
+impl Sdf for SmoothUnion<'_> {
+    fn distance(&self, point: &Point3) -> f32 {
+        let d1 = self.a.distance(point);
+        let d2 = self.b.distance(point);
+        if self.smoothness <= 0.0 {
+            return d1.min(d2);
+        }
+
+        // Polynomial smooth minimum
+        let h = (0.5 + 0.5 * (d2 - d1) / self.smoothness).clamp(0.0, 1.0);
+        d2 + (d1 - d2) * h - self.smoothness * h * (1.0 - h)
+    }
+}
+
+/// Twists the shape around the y-axis by `rate` radians per unit of height.
+///
+/// The result is no longer an exact distance, so objects using it should march with a reduced
+/// step size.
+#[derive(Clone)]
+pub struct Twist<'a> {
+    sdf: BoxedSdf<'a>,
+    rate: f32,
+}
+
+impl<'a> Twist<'a> {
+    pub fn new(sdf: impl Sdf + Send + Sync + 'a, rate: f32) -> Self {
+        Self {
+            sdf: Box::new(sdf),
+            rate,
+        }
+    }
+}
+
+impl Sdf for Twist<'_> {
+    fn distance(&self, point: &Point3) -> f32 {
+        let angle = self.rate * point.y();
+        let (sin, cos) = angle.sin_cos();
+        let twisted = Point3::new(
+            cos * point.x() - sin * point.z(),
+            point.y(),
+            sin * point.x() + cos * point.z(),
+        );
+
+        self.sdf.distance(&twisted)
+    }
+}
+
+/// Repeats the shape infinitely with the given period along each axis.
+/// A period of zero disables repetition along that axis.
+///
+/// The shape should fit into a single cell, centered at the origin.
+#[derive(Clone)]
+pub struct Repetition<'a> {
+    sdf: BoxedSdf<'a>,
+    period: Vec3,
+}
+
+impl<'a> Repetition<'a> {
+    pub fn new(sdf: impl Sdf + Send + Sync + 'a, period: Vec3) -> Self {
+        Self {
+            sdf: Box::new(sdf),
+            period,
+        }
+    }
+}
+
+impl Sdf for Repetition<'_> {
+    fn distance(&self, point: &Point3) -> f32 {
+        let repeat = |x: f32, period: f32| {
+            if period > 0.0 {
+                x - period * (x / period).round()
+            } else {
+                x
+            }
+        };
+        let local = Point3::new(
+            repeat(point.x(), self.period.x()),
+            repeat(point.y(), self.period.y()),
+            repeat(point.z(), self.period.z()),
+        );
+
+        self.sdf.distance(&local)
+    }
+}
+
+#[derive(Clone)]
+pub struct Translation<'a> {
+    sdf: BoxedSdf<'a>,
+    offset: Vec3,
+}
+
+impl<'a> Translation<'a> {
+    pub fn new(sdf: impl Sdf + Send + Sync + 'a, offset: Vec3) -> Self {
+        Self {
+            sdf: Box::new(sdf),
+            offset,
+        }
+    }
+}
+
+impl Sdf for Translation<'_> {
+    fn distance(&self, point: &Point3) -> f32 {
+        self.sdf.distance(&(point - self.offset))
+    }
+}
@@ -0,0 +1,28 @@
+mod combinators;
+mod primitives;
+
+pub use combinators::{
+    Difference, Intersection, Repetition, SmoothUnion, Translation, Twist, Union,
+};
+pub use primitives::{BoxSdf, CapsuleSdf, SphereSdf, TorusSdf};
+
+use dyn_clonable::clonable;
+
+use crate::vec3::Point3;
+
+/// A signed distance function: negative inside the shape, positive outside.
+#[clonable]
+pub trait Sdf: Clone {
+    fn distance(&self, point: &Point3) -> f32;
+}
+
+pub type BoxedSdf<'a> = Box<dyn Sdf + Send + Sync + 'a>;
+
+impl<F> Sdf for F
+where
+    F: Fn(&Point3) -> f32 + Clone,
+{
+    fn distance(&self, point: &Point3) -> f32 {
+        self(point)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::vec3::{Point3, Vec3};
+
+use super::Sdf;
+
+#[derive(Debug, Clone)]
+pub struct SphereSdf {
+    center: Point3,
+    radius: f32,
+}
+
+impl SphereSdf {
+    pub fn new(center: Point3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+}
+
+impl Sdf for SphereSdf {
+    fn distance(&self, point: &Point3) -> f32 {
+        (point - self.center).length() - self.radius
+    }
+}
+
+/// Axis aligned box, optionally with rounded edges.
+#[derive(Debug, Clone)]
+pub struct BoxSdf {
+    center: Point3,
+    half_extents: Vec3,
+    rounding: f32,
+}
+
+impl BoxSdf {
+    pub fn new(center: Point3, half_extents: Vec3) -> Self {
+        Self::rounded(center, half_extents, 0.0)
+    }
+
+    pub fn rounded(center: Point3, half_extents: Vec3, rounding: f32) -> Self {
+        Self {
+            center,
+            half_extents,
+            rounding,
+        }
+    }
+}
+
+impl Sdf for BoxSdf {
+    fn distance(&self, point: &Point3) -> f32 {
+        let p = point - self.center;
+        let q = Vec3::new(
+            p.x().abs() - self.half_extents.x() + self.rounding,
+            p.y().abs() - self.half_extents.y() + self.rounding,
+            p.z().abs() - self.half_extents.z() + self.rounding,
+        );
+        let outside = Vec3::new(q.x().max(0.0), q.y().max(0.0), q.z().max(0.0)).length();
+        let inside = q.x().max(q.y()).max(q.z()).min(0.0);
+
+        outside + inside - self.rounding
+    }
+}
+
+/// Torus lying in the xz-plane.
+#[derive(Debug, Clone)]
+pub struct TorusSdf {
+    center: Point3,
+    major_radius: f32,
+    minor_radius: f32,
+}
+
+impl TorusSdf {
+    pub fn new(center: Point3, major_radius: f32, minor_radius: f32) -> Self {
+        Self {
+            center,
+            major_radius,
+            minor_radius,
+        }
+    }
+}
+
+impl Sdf for TorusSdf {
+    fn distance(&self, point: &Point3) -> f32 {
+        let p = point - self.center;
+        let ring = (p.x() * p.x() + p.z() * p.z()).sqrt() - self.major_radius;
+
+        (ring * ring + p.y() * p.y()).sqrt() - self.minor_radius
+    }
+}
+
+/// Line segment from `a` to `b` with rounded caps.
+#[derive(Debug, Clone)]
+pub struct CapsuleSdf {
+    a: Point3,
+    b: Point3,
+    radius: f32,
+}
+
+impl CapsuleSdf {
+    pub fn new(a: Point3, b: Point3, radius: f32) -> Self {
+        Self { a, b, radius }
+    }
+}
+
+impl Sdf for CapsuleSdf {
+    fn distance(&self, point: &Point3) -> f32 {
+        let pa = point - self.a;
+        let ba = self.b - self.a;
+        let h = (pa.dot(ba) / ba.length_squared()).clamp(0.0, 1.0);
+
+        (pa - ba * h).length() - self.radius
+    }
+}
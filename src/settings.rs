@@ -21,20 +21,101 @@ pub struct ImageSettings {
     pub image_height: usize,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct SceneSettings {
     pub background: Color,
+    /// Image lighting the scene from all directions, which replaces the background.
+    pub environment: Option<EnvironmentSettings>,
+    /// Daylight sky and sun, which replace the background unless an environment map is set.
+    pub sky: Option<SkySettings>,
     pub scene_type: SceneType,
     pub time: Time,
 }
 
+#[derive(Debug, Clone)]
+pub struct EnvironmentSettings {
+    /// Path to an equirectangular `.hdr` or `.exr` image.
+    pub path: String,
+    /// Rotation around the vertical axis, in radians.
+    pub rotation: f32,
+    pub intensity: f32,
+}
+
+impl EnvironmentSettings {
+    /// Lights the scene with the image at `path`, unrotated and at its original brightness.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            rotation: 0.0,
+            intensity: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SkySettings {
+    /// Angle of the sun above the horizon, in radians.
+    pub sun_elevation: f32,
+    /// Angle of the sun around the vertical axis from the x axis towards the z axis, in
+    /// radians.
+    pub sun_azimuth: f32,
+    /// Haziness of the air, from 2 for a very clear sky to around 10 for a hazy one.
+    pub turbidity: f32,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct RendererSettings {
     pub samples_per_pixel: usize,
     pub max_depth: usize,
+    /// Trace wavelengths instead of RGB, which is slower but renders dispersion.
+    pub spectral: bool,
 }
 
+/// How an image texture is sampled.
 #[derive(Debug, Clone, Copy)]
+pub struct TextureSettings {
+    /// What lies beyond the edges of the image.
+    pub wrap: WrapMode,
+    pub filter: TextureFilter,
+    /// Blend between downsampled copies of the image where a pixel covers many texels,
+    /// which keeps detailed textures from shimmering in the distance.
+    pub mipmaps: bool,
+    /// How the values of 8 and 16 bit images are encoded. Float images such as `.hdr` and
+    /// `.exr` files are always linear.
+    pub color_space: ColorSpace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Tiles the image.
+    Repeat,
+    /// Tiles the image, flipping every other copy so that the edges meet seamlessly.
+    Mirror,
+    /// Extends the pixels on the edges.
+    Clamp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureFilter {
+    /// Takes the closest texel, which keeps pixel art sharp.
+    Nearest,
+    /// Interpolates linearly between the four closest texels.
+    Bilinear,
+    /// Interpolates smoothly between the sixteen closest texels, which looks better when
+    /// a texture is magnified a lot.
+    Bicubic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    /// Gamma encoded like most color images, which spends more of the values on dark
+    /// shades.
+    Srgb,
+    /// Stored as is, like normal maps and other data that isn't a color.
+    Linear,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct Settings {
     pub camera: CameraSettings,
     pub image: ImageSettings,
@@ -70,6 +151,76 @@ impl Settings {
                 settings.camera.vfov = 40.0;
                 settings.scene.background = Color::new(0.0, 0.0, 0.0);
             }
+            SceneType::Terrain => {
+                settings.camera.cam_pos = Point3::new(0.0, 9.0, 20.0);
+                settings.camera.look_at = Point3::new(0.0, 1.0, 0.0);
+                settings.camera.vfov = 45.0;
+            }
+            SceneType::Foliage => {
+                settings.camera.cam_pos = Point3::new(0.0, 2.5, 14.0);
+                settings.camera.look_at = Point3::new(0.0, 2.5, 0.0);
+                settings.camera.vfov = 40.0;
+            }
+            SceneType::Metals
+            | SceneType::FrostedGlass
+            | SceneType::TintedGlass
+            | SceneType::PrincipledSpheres
+            | SceneType::MixedMaterials
+            | SceneType::CoatedSpheres
+            | SceneType::RoughDiffuse
+            | SceneType::Iridescence
+            | SceneType::SubsurfaceSpheres => {
+                settings.camera.cam_pos = Point3::new(14.0, 5.0, 0.0);
+                settings.camera.look_at = Point3::new(0.0, 0.8, 0.0);
+                settings.camera.vfov = 35.0;
+            }
+            SceneType::DeltaLights => {
+                settings.camera.cam_pos = Point3::new(14.0, 5.0, 0.0);
+                settings.camera.look_at = Point3::new(0.0, 0.8, 0.0);
+                settings.camera.vfov = 35.0;
+                settings.scene.background = Color::new(0.05, 0.06, 0.1);
+            }
+            SceneType::EnvironmentLighting => {
+                settings.camera.cam_pos = Point3::new(14.0, 3.0, 0.0);
+                settings.camera.look_at = Point3::new(0.0, 1.0, 0.0);
+                settings.camera.vfov = 35.0;
+                settings.scene.environment =
+                    Some(EnvironmentSettings::new("assets/environment.hdr"));
+            }
+            SceneType::Daylight => {
+                settings.camera.cam_pos = Point3::new(16.0, 4.0, 9.0);
+                settings.camera.look_at = Point3::new(0.0, 1.6, 0.0);
+                settings.camera.vfov = 32.0;
+                settings.scene.sky = Some(SkySettings {
+                    sun_elevation: 35f32.to_radians(),
+                    sun_azimuth: 40f32.to_radians(),
+                    turbidity: 3.0,
+                });
+            }
+            SceneType::IesLights => {
+                settings.camera.cam_pos = Point3::new(9.0, 2.0, 0.0);
+                settings.camera.look_at = Point3::new(0.0, 1.6, 0.0);
+                settings.camera.vfov = 45.0;
+                settings.scene.background = Color::new(0.0, 0.0, 0.0);
+            }
+            SceneType::EmissiveTextures => {
+                settings.camera.cam_pos = Point3::new(9.0, 2.0, 0.0);
+                settings.camera.look_at = Point3::new(0.0, 1.6, 0.8);
+                settings.camera.vfov = 40.0;
+                settings.scene.background = Color::new(0.0, 0.0, 0.0);
+            }
+            SceneType::TextureFiltering => {
+                settings.camera.cam_pos = Point3::new(0.0, 1.6, 9.0);
+                settings.camera.look_at = Point3::new(0.0, 1.2, -8.0);
+                settings.camera.vfov = 45.0;
+            }
+            SceneType::Dispersion => {
+                settings.camera.cam_pos = Point3::new(12.0, 2.5, 0.0);
+                settings.camera.look_at = Point3::new(0.0, 1.4, 0.0);
+                settings.camera.vfov = 35.0;
+                settings.scene.background = Color::new(0.02, 0.02, 0.03);
+                settings.renderer.spectral = true;
+            }
             _ => {}
         }
 
@@ -108,6 +259,8 @@ impl Default for SceneSettings {
     fn default() -> Self {
         Self {
             background: Color::new(0.7, 0.8, 1.0),
+            environment: None,
+            sky: None,
             scene_type: SceneType::SimpleLight,
             time: Time::new(0.0, 1.0),
         }
@@ -119,17 +272,18 @@ impl Default for RendererSettings {
         Self {
             samples_per_pixel: 500,
             max_depth: 50,
+            spectral: false,
         }
     }
 }
 
-impl Default for Settings {
+impl Default for TextureSettings {
     fn default() -> Self {
         Self {
-            camera: CameraSettings::default(),
-            image: ImageSettings::default(),
-            scene: SceneSettings::default(),
-            renderer: RendererSettings::default(),
+            wrap: WrapMode::Clamp,
+            filter: TextureFilter::Bilinear,
+            mipmaps: true,
+            color_space: ColorSpace::Srgb,
         }
     }
 }
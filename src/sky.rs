@@ -0,0 +1,176 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use crate::{
+    color::Color, environment::EnvironmentMap, lights::Light, settings::SkySettings, spectrum,
+    vec3::Vec3,
+};
+
+/// Luminance in kcd/m² that corresponds to a radiance of one, which keeps sunlit white
+/// surfaces within the range of the image. A clear sky at noon has a zenith luminance of
+/// around 10 kcd/m².
+const LUMINANCE_UNIT: f32 = 30.0;
+
+/// Illuminance of the sun above the atmosphere, in klux.
+const SUN_ILLUMINANCE: f32 = 128.0;
+
+/// Angular diameter of the sun in degrees.
+const SUN_ANGULAR_DIAMETER: f32 = 0.53;
+
+/// Resolution of the environment map the sky is baked into. The sky is smooth, so it
+/// only needs to be fine enough for the glow around the sun.
+const MAP_WIDTH: usize = 512;
+const MAP_HEIGHT: usize = 256;
+
+/// Clear daylight sky after Preetham, Shirley and Smits, "A Practical Analytic Model for
+/// Daylight" (1999). The sky is brightest around the sun and towards the horizon, and turns
+/// hazier and whiter with increasing turbidity.
+#[derive(Debug, Clone, Copy)]
+pub struct Sky {
+    /// Unit vector pointing towards the sun.
+    sun_direction: Vec3,
+    turbidity: f32,
+    /// Perez distributions of the luminance and the x and y chromaticities.
+    luminance: Perez,
+    x: Perez,
+    y: Perez,
+}
+
+/// Distribution of a quantity over the sky relative to its value at the zenith.
+#[derive(Debug, Clone, Copy)]
+struct Perez {
+    coefficients: [f32; 5],
+    /// Value at the zenith divided by the distribution at the zenith.
+    scale: f32,
+}
+
+impl Perez {
+    fn new(coefficients: [f32; 5], zenith: f32, sun_zenith_angle: f32) -> Self {
+        let mut perez = Self {
+            coefficients,
+            scale: 1.0,
+        };
+        perez.scale = zenith / perez.distribution(1.0, sun_zenith_angle);
+        perez
+    }
+
+    /// Value in a direction at `cos_theta` from the zenith and `gamma` radians from the sun.
+    fn value(&self, cos_theta: f32, gamma: f32) -> f32 {
+        self.scale * self.distribution(cos_theta, gamma)
+    }
+
+    fn distribution(&self, cos_theta: f32, gamma: f32) -> f32 {
+        let [a, b, c, d, e] = self.coefficients;
+        let cos_gamma = gamma.cos();
+        (1.0 + a * (b / cos_theta).exp())
+            * (1.0 + c * (d * gamma).exp() + e * cos_gamma * cos_gamma)
+    }
+}
+
+impl Sky {
+    /// The sun is placed in the sky by its elevation above the horizon and its azimuth
+    /// around the vertical axis, starting at the x axis and turning towards the z axis. The
+    /// model doesn't cover twilight, so the sun is kept above the horizon.
+    pub fn from_settings(settings: SkySettings) -> Self {
+        let elevation = settings.sun_elevation.clamp(0.0, FRAC_PI_2);
+        let azimuth = settings.sun_azimuth;
+        // The fit doesn't extend to skies clearer than this
+        let t = settings.turbidity.max(2.0);
+
+        let sun_direction = Vec3::new(
+            elevation.cos() * azimuth.cos(),
+            elevation.sin(),
+            elevation.cos() * azimuth.sin(),
+        );
+
+        let theta = FRAC_PI_2 - elevation;
+        let (theta2, theta3) = (theta * theta, theta * theta * theta);
+        let chi = (4.0 / 9.0 - t / 120.0) * (PI - 2.0 * theta);
+        let zenith_luminance = ((4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192).max(0.0);
+        let zenith_x = t * t * (0.00166 * theta3 - 0.00375 * theta2 + 0.00209 * theta)
+            + t * (-0.02903 * theta3 + 0.06377 * theta2 - 0.03202 * theta + 0.00394)
+            + (0.11693 * theta3 - 0.21196 * theta2 + 0.06052 * theta + 0.25886);
+        let zenith_y = t * t * (0.00275 * theta3 - 0.00610 * theta2 + 0.00317 * theta)
+            + t * (-0.04214 * theta3 + 0.08970 * theta2 - 0.04153 * theta + 0.00516)
+            + (0.15346 * theta3 - 0.26756 * theta2 + 0.06670 * theta + 0.26688);
+
+        let luminance = [
+            0.1787 * t - 1.4630,
+            -0.3554 * t + 0.4275,
+            -0.0227 * t + 5.3251,
+            0.1206 * t - 2.5771,
+            -0.0670 * t + 0.3703,
+        ];
+        let x = [
+            -0.0193 * t - 0.2592,
+            -0.0665 * t + 0.0008,
+            -0.0004 * t + 0.2125,
+            -0.0641 * t - 0.8989,
+            -0.0033 * t + 0.0452,
+        ];
+        let y = [
+            -0.0167 * t - 0.2608,
+            -0.0950 * t + 0.0092,
+            -0.0079 * t + 0.2102,
+            -0.0441 * t - 1.6537,
+            -0.0109 * t + 0.0529,
+        ];
+
+        Self {
+            sun_direction,
+            turbidity: t,
+            luminance: Perez::new(luminance, zenith_luminance, theta),
+            x: Perez::new(x, zenith_x, theta),
+            y: Perez::new(y, zenith_y, theta),
+        }
+    }
+
+    /// Radiance of the sky arriving from `direction`, without the sun itself. Below the
+    /// horizon the sky continues with the color at the horizon.
+    pub fn radiance(&self, direction: Vec3) -> Color {
+        let direction = direction.normalized();
+        let cos_theta = direction.y().max(0.001);
+        let gamma = direction.dot(self.sun_direction).clamp(-1.0, 1.0).acos();
+
+        let luminance = self.luminance.value(cos_theta, gamma);
+        let x = self.x.value(cos_theta, gamma);
+        let y = self.y.value(cos_theta, gamma);
+        if y <= 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+
+        let rgb = spectrum::xyz_to_rgb(x / y * luminance, luminance, (1.0 - x - y) / y * luminance);
+        rgb.map(|value| value.max(0.0) / LUMINANCE_UNIT)
+    }
+
+    /// The sky as an environment map, so it can be sampled for direct lighting.
+    pub fn environment_map(&self) -> EnvironmentMap {
+        EnvironmentMap::from_fn(MAP_WIDTH, MAP_HEIGHT, |direction| self.radiance(direction))
+    }
+
+    /// The sun matching the sky, dimmed and reddened by the air it shines through.
+    pub fn sun(&self) -> Light {
+        let transmittance = spectrum::reflectance_to_rgb(|lambda| self.sun_transmittance(lambda));
+        Light::sun(
+            self.sun_direction,
+            transmittance * (SUN_ILLUMINANCE / LUMINANCE_UNIT),
+            SUN_ANGULAR_DIAMETER.to_radians(),
+        )
+    }
+
+    /// Fraction of sunlight of the wavelength `lambda`, in nanometers, that passes through
+    /// the atmosphere by Rayleigh scattering on air molecules and Ångström's formula for
+    /// haze. Absorption by ozone and water vapor is left out.
+    fn sun_transmittance(&self, lambda: f32) -> f32 {
+        // Relative optical mass, the length of the path through the air compared to the
+        // path straight up, which stays finite at the horizon
+        let zenith_angle = self.sun_direction.y().clamp(0.0, 1.0).acos();
+        let mass =
+            1.0 / (zenith_angle.cos() + 0.15 * (93.885 - zenith_angle.to_degrees()).powf(-1.253));
+
+        let micrometers = lambda / 1000.0;
+        let rayleigh = (-0.008735 * micrometers.powf(-4.08) * mass).exp();
+        let beta = 0.04608 * self.turbidity - 0.04586;
+        let haze = (-beta * micrometers.powf(-1.3) * mass).exp();
+        rayleigh * haze
+    }
+}
@@ -0,0 +1,310 @@
+extern crate overload;
+use overload::overload;
+use std::ops;
+use std::sync::OnceLock;
+
+use crate::color::Color;
+
+/// Number of wavelengths carried along every path.
+pub const WAVELENGTH_SAMPLES: usize = 4;
+
+const LAMBDA_MIN: f32 = 360.0;
+const LAMBDA_MAX: f32 = 830.0;
+
+/// Wavelengths in nanometers carried along a path, together with the probability density
+/// they were sampled with. The first one is the hero wavelength, which survives when
+/// dispersion forces the path to pick a single wavelength.
+/// Reference: Wilkie et al., "Hero Wavelength Spectral Sampling" (2014).
+#[derive(Debug, Clone, Copy)]
+pub struct SampledWavelengths {
+    lambda: [f32; WAVELENGTH_SAMPLES],
+    pdf: [f32; WAVELENGTH_SAMPLES],
+}
+
+impl SampledWavelengths {
+    /// Samples the hero wavelength proportionally to the sensitivity of the eye and spaces
+    /// the other ones evenly across the visible range.
+    pub fn sample_visible(u: f32) -> Self {
+        let mut lambda = [0.0; WAVELENGTH_SAMPLES];
+        let mut pdf = [0.0; WAVELENGTH_SAMPLES];
+        for i in 0..WAVELENGTH_SAMPLES {
+            let u = (u + i as f32 / WAVELENGTH_SAMPLES as f32).fract();
+            lambda[i] = sample_visible_wavelength(u);
+            pdf[i] = visible_wavelength_pdf(lambda[i]);
+        }
+
+        Self { lambda, pdf }
+    }
+
+    pub fn hero(&self) -> f32 {
+        self.lambda[0]
+    }
+
+    /// Drops all but the hero wavelength, which is needed once the path depends on the
+    /// wavelength, for instance after refracting through a dispersive material.
+    pub fn terminate_secondary(&mut self) {
+        if self.pdf[1..].iter().all(|&pdf| pdf == 0.0) {
+            return;
+        }
+
+        self.pdf[0] /= WAVELENGTH_SAMPLES as f32;
+        for pdf in &mut self.pdf[1..] {
+            *pdf = 0.0;
+        }
+    }
+}
+
+/// Sample density from Radziszewski et al., "An Improved Technique for Full Spectral
+/// Rendering" (2009), which follows the eye's sensitivity more closely than uniform sampling.
+fn sample_visible_wavelength(u: f32) -> f32 {
+    538.0 - 138.888_89 * (0.856_910_6 - 1.827_502 * u).atanh()
+}
+
+fn visible_wavelength_pdf(lambda: f32) -> f32 {
+    if !(LAMBDA_MIN..=LAMBDA_MAX).contains(&lambda) {
+        return 0.0;
+    }
+    0.003_939_804 / (0.0072 * (lambda - 538.0)).cosh().powi(2)
+}
+
+/// Values of a spectrum at the sampled wavelengths of a path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampledSpectrum([f32; WAVELENGTH_SAMPLES]);
+
+impl SampledSpectrum {
+    pub fn constant(value: f32) -> Self {
+        Self([value; WAVELENGTH_SAMPLES])
+    }
+
+    /// Upsamples an RGB color to a smooth spectrum and evaluates it at the given wavelengths.
+    /// Reference: Smits, "An RGB to Spectrum Conversion for Reflectances" (1999).
+    pub fn from_rgb(color: Color, wavelengths: &SampledWavelengths) -> Self {
+        Self(wavelengths.lambda.map(|lambda| smits(color, lambda)))
+    }
+
+    /// Converts the spectral radiance back to linear RGB, through the CIE XYZ color space.
+    pub fn to_rgb(self, wavelengths: &SampledWavelengths) -> Color {
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        for i in 0..WAVELENGTH_SAMPLES {
+            if wavelengths.pdf[i] == 0.0 {
+                continue;
+            }
+            let value = self.0[i] / wavelengths.pdf[i];
+            let (cx, cy, cz) = cie_xyz(wavelengths.lambda[i]);
+            x += cx * value;
+            y += cy * value;
+            z += cz * value;
+        }
+
+        let rgb = xyz_to_rgb(x, y, z) / WAVELENGTH_SAMPLES as f32;
+        let white = white_point();
+        Color::new(
+            rgb.r() / white.r(),
+            rgb.g() / white.g(),
+            rgb.b() / white.b(),
+        )
+    }
+}
+
+overload!((a: ?SampledSpectrum) + (b: ?SampledSpectrum) -> SampledSpectrum {
+    SampledSpectrum(std::array::from_fn(|i| a.0[i] + b.0[i]))
+});
+overload!((a: ?SampledSpectrum) * (b: ?SampledSpectrum) -> SampledSpectrum {
+    SampledSpectrum(std::array::from_fn(|i| a.0[i] * b.0[i]))
+});
+overload!((a: &mut SampledSpectrum) += (b: ?SampledSpectrum) {
+    for i in 0..WAVELENGTH_SAMPLES { a.0[i] += b.0[i]; }
+});
+overload!((a: &mut SampledSpectrum) *= (b: ?SampledSpectrum) {
+    for i in 0..WAVELENGTH_SAMPLES { a.0[i] *= b.0[i]; }
+});
+
+/// How the colors along a path are represented.
+#[derive(Debug, Clone, Copy)]
+pub enum PathSampling {
+    /// The first three values of a `SampledSpectrum` hold red, green and blue.
+    Rgb,
+    Spectral(SampledWavelengths),
+}
+
+impl PathSampling {
+    pub fn spectral() -> Self {
+        Self::Spectral(SampledWavelengths::sample_visible(rand::random()))
+    }
+
+    pub fn wavelength(&self) -> Option<f32> {
+        match self {
+            Self::Rgb => None,
+            Self::Spectral(wavelengths) => Some(wavelengths.hero()),
+        }
+    }
+
+    pub fn terminate_secondary(&mut self) {
+        if let Self::Spectral(wavelengths) = self {
+            wavelengths.terminate_secondary();
+        }
+    }
+
+    /// Converts an RGB color to the representation used along the path.
+    pub fn upsample(&self, color: Color) -> SampledSpectrum {
+        match self {
+            Self::Rgb => SampledSpectrum([color.r(), color.g(), color.b(), 0.0]),
+            Self::Spectral(wavelengths) => SampledSpectrum::from_rgb(color, wavelengths),
+        }
+    }
+
+    /// Converts the radiance gathered along the path back to RGB.
+    pub fn resolve(&self, spectrum: SampledSpectrum) -> Color {
+        match self {
+            Self::Rgb => Color::new(spectrum.0[0], spectrum.0[1], spectrum.0[2]),
+            Self::Spectral(wavelengths) => spectrum.to_rgb(wavelengths),
+        }
+    }
+}
+
+/// Spacing in nanometers of the wavelengths at which `reflectance_to_rgb` evaluates spectra.
+const REFLECTANCE_STEP: f32 = 10.0;
+
+/// Converts a reflectance spectrum, given as a function of the wavelength in nanometers, to
+/// linear RGB by integrating it against the CIE color matching functions over the visible
+/// range. A constant spectrum maps to a gray of the same value, and colors outside of the
+/// sRGB gamut are clamped to it.
+pub fn reflectance_to_rgb(reflectance: impl Fn(f32) -> f32) -> Color {
+    let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+    let mut lambda = 380.0;
+    while lambda <= 780.0 {
+        let value = reflectance(lambda);
+        let (cx, cy, cz) = cie_xyz(lambda);
+        x += cx * value;
+        y += cy * value;
+        z += cz * value;
+        lambda += REFLECTANCE_STEP;
+    }
+
+    let rgb = xyz_to_rgb(x, y, z);
+    let white = reflectance_white_point();
+    Color::new(
+        (rgb.r() / white.r()).max(0.0),
+        (rgb.g() / white.g()).max(0.0),
+        (rgb.b() / white.b()).max(0.0),
+    )
+}
+
+/// RGB of a constant spectrum of one, sampled like in `reflectance_to_rgb`.
+fn reflectance_white_point() -> Color {
+    static WHITE_POINT: OnceLock<Color> = OnceLock::new();
+
+    *WHITE_POINT.get_or_init(|| {
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        let mut lambda = 380.0;
+        while lambda <= 780.0 {
+            let (cx, cy, cz) = cie_xyz(lambda);
+            x += cx;
+            y += cy;
+            z += cz;
+            lambda += REFLECTANCE_STEP;
+        }
+        xyz_to_rgb(x, y, z)
+    })
+}
+
+/// Smits' basis spectra, sampled in 10 bins evenly spread over 380 to 720 nm.
+const SMITS_WHITE: [f32; 10] = [
+    1.0000, 1.0000, 0.9999, 0.9993, 0.9992, 0.9998, 1.0000, 1.0000, 1.0000, 1.0000,
+];
+const SMITS_CYAN: [f32; 10] = [
+    0.9710, 0.9426, 1.0007, 1.0007, 1.0007, 1.0007, 0.1564, 0.0000, 0.0000, 0.0000,
+];
+const SMITS_MAGENTA: [f32; 10] = [
+    1.0000, 1.0000, 0.9685, 0.2229, 0.0000, 0.0458, 0.8369, 1.0000, 1.0000, 0.9959,
+];
+const SMITS_YELLOW: [f32; 10] = [
+    0.0001, 0.0000, 0.1088, 0.6651, 1.0000, 1.0000, 0.9996, 0.9586, 0.9685, 0.9840,
+];
+const SMITS_RED: [f32; 10] = [
+    0.1012, 0.0515, 0.0000, 0.0000, 0.0000, 0.0000, 0.8325, 1.0149, 1.0149, 1.0149,
+];
+const SMITS_GREEN: [f32; 10] = [
+    0.0000, 0.0000, 0.0273, 0.7937, 1.0000, 0.9418, 0.1719, 0.0000, 0.0000, 0.0025,
+];
+const SMITS_BLUE: [f32; 10] = [
+    1.0000, 1.0000, 0.8916, 0.3323, 0.0000, 0.0000, 0.0003, 0.0369, 0.0483, 0.0496,
+];
+
+fn smits(color: Color, lambda: f32) -> f32 {
+    // Linear interpolation between the bin centers
+    let bin_width = (720.0 - 380.0) / 10.0;
+    let x = ((lambda - 380.0) / bin_width - 0.5).clamp(0.0, 9.0);
+    let i = (x as usize).min(8);
+    let t = x - i as f32;
+    let basis = |spectrum: &[f32; 10]| spectrum[i] + t * (spectrum[i + 1] - spectrum[i]);
+
+    let (r, g, b) = (color.r(), color.g(), color.b());
+    if r <= g && r <= b {
+        let rest = if g <= b {
+            (g - r) * basis(&SMITS_CYAN) + (b - g) * basis(&SMITS_BLUE)
+        } else {
+            (b - r) * basis(&SMITS_CYAN) + (g - b) * basis(&SMITS_GREEN)
+        };
+        r * basis(&SMITS_WHITE) + rest
+    } else if g <= r && g <= b {
+        let rest = if r <= b {
+            (r - g) * basis(&SMITS_MAGENTA) + (b - r) * basis(&SMITS_BLUE)
+        } else {
+            (b - g) * basis(&SMITS_MAGENTA) + (r - b) * basis(&SMITS_RED)
+        };
+        g * basis(&SMITS_WHITE) + rest
+    } else {
+        let rest = if r <= g {
+            (r - b) * basis(&SMITS_YELLOW) + (g - r) * basis(&SMITS_GREEN)
+        } else {
+            (g - b) * basis(&SMITS_YELLOW) + (r - g) * basis(&SMITS_RED)
+        };
+        b * basis(&SMITS_WHITE) + rest
+    }
+}
+
+/// Analytic fit of the CIE 1931 color matching functions.
+/// Reference: Wyman, Sloan and Shirley, "Simple Analytic Approximations to the CIE XYZ
+/// Color Matching Functions" (2013).
+fn cie_xyz(lambda: f32) -> (f32, f32, f32) {
+    let g = |mu: f32, sigma_low: f32, sigma_high: f32| {
+        let sigma = if lambda < mu { sigma_low } else { sigma_high };
+        let t = (lambda - mu) / sigma;
+        (-0.5 * t * t).exp()
+    };
+
+    let x =
+        1.056 * g(599.8, 37.9, 31.0) + 0.362 * g(442.0, 16.0, 26.7) - 0.065 * g(501.1, 20.4, 26.2);
+    let y = 0.821 * g(568.8, 46.9, 40.5) + 0.286 * g(530.9, 16.3, 31.1);
+    let z = 1.217 * g(437.0, 11.8, 36.0) + 0.681 * g(459.0, 26.0, 13.8);
+    (x, y, z)
+}
+
+/// Converts CIE XYZ to linear sRGB.
+pub fn xyz_to_rgb(x: f32, y: f32, z: f32) -> Color {
+    Color::new(
+        3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+        -0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z,
+        0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+    )
+}
+
+/// RGB of the upsampled white spectrum, which scales the output so white stays white.
+fn white_point() -> Color {
+    static WHITE_POINT: OnceLock<Color> = OnceLock::new();
+
+    *WHITE_POINT.get_or_init(|| {
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        let mut lambda = LAMBDA_MIN;
+        while lambda <= LAMBDA_MAX {
+            let value = smits(Color::new(1.0, 1.0, 1.0), lambda);
+            let (cx, cy, cz) = cie_xyz(lambda);
+            x += cx * value;
+            y += cy * value;
+            z += cz * value;
+            lambda += 1.0;
+        }
+        xyz_to_rgb(x, y, z)
+    })
+}
@@ -17,14 +17,24 @@ impl<'a> Checker<'a> {
     }
 }
 
-impl Texture for Checker<'_> {
-    fn value(&self, u: f32, v: f32, point: &Vec3) -> Color {
+impl Checker<'_> {
+    fn square(&self, point: &Vec3) -> &BoxedTexture<'_> {
         let sines = (10.0 * point.x()).sin() * (10.0 * point.y()).sin() * (10.0 * point.z()).sin();
 
         if sines < 0.0 {
-            self.odd.value(u, v, point)
+            &self.odd
         } else {
-            self.even.value(u, v, point)
+            &self.even
         }
     }
 }
+
+impl Texture for Checker<'_> {
+    fn value(&self, u: f32, v: f32, point: &Vec3) -> Color {
+        self.square(point).value(u, v, point)
+    }
+
+    fn filtered(&self, u: f32, v: f32, point: &Vec3, width: f32) -> Color {
+        self.square(point).filtered(u, v, point, width)
+    }
+}
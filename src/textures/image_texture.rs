@@ -1,33 +1,311 @@
-use crate::color::Color;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use image::{
+    codecs::hdr::HdrDecoder, ColorType, DynamicImage, ImageError, ImageResult, Rgb32FImage,
+};
+use thiserror::Error;
+
+use crate::{
+    color::Color,
+    settings::{ColorSpace, TextureFilter, TextureSettings, WrapMode},
+    vec3::Vec3,
+};
 
 use super::Texture;
 
+#[derive(Error, Debug)]
+pub enum TextureError {
+    #[error("Failed to load the texture {path}: {source}")]
+    Image {
+        path: String,
+        #[source]
+        source: ImageError,
+    },
+}
+
+/// Texture read from an image file. Besides the image itself it keeps a pyramid of copies
+/// that halve in size, between which lookups blend to match the footprint of a ray.
+///
+/// Clones share the image, which can also be shared between textures loaded through a
+/// [`TextureCache`].
 #[derive(Clone)]
 pub struct ImageTexture {
-    image: image::RgbImage,
+    /// The image followed by its downsampled copies, down to a single texel.
+    levels: Arc<Vec<MipLevel>>,
+    settings: TextureSettings,
+}
+
+#[derive(Clone)]
+struct MipLevel {
+    width: usize,
+    height: usize,
+    /// RGBA values in rows from the top of the image.
+    texels: Vec<[f32; 4]>,
 }
 
 impl ImageTexture {
-    pub fn new(filename: &str) -> Self {
-        let image = image::open(filename).unwrap().to_rgb8();
-        Self { image }
+    pub fn new(filename: &str) -> Result<Self, TextureError> {
+        Self::from_settings(filename, TextureSettings::default())
+    }
+
+    /// Loads the image and decodes it to linear values, which the mip pyramid has to be
+    /// built from for the levels to keep the brightness of the image.
+    pub fn from_settings(filename: &str, settings: TextureSettings) -> Result<Self, TextureError> {
+        let image = open_image(filename).map_err(|source| TextureError::Image {
+            path: filename.to_string(),
+            source,
+        })?;
+        let is_float = matches!(image.color(), ColorType::Rgb32F | ColorType::Rgba32F);
+        let decode = match settings.color_space {
+            ColorSpace::Srgb if !is_float => srgb_to_linear,
+            _ => |value| value,
+        };
+
+        let image = image.into_rgba32f();
+        let base = MipLevel {
+            width: image.width() as usize,
+            height: image.height() as usize,
+            texels: image
+                .pixels()
+                .map(|&image::Rgba([r, g, b, a])| [decode(r), decode(g), decode(b), a])
+                .collect(),
+        };
+
+        Ok(Self {
+            levels: Arc::new(mip_pyramid(base)),
+            settings,
+        })
+    }
+
+    /// Averages the texels in a square of `width` in texture coordinates around `(u, v)`
+    /// by blending the two levels of the pyramid whose texels are closest in size.
+    fn sample(&self, u: f32, v: f32, width: f32) -> [f32; 4] {
+        let base = &self.levels[0];
+        if !self.settings.mipmaps || width <= 0.0 || self.levels.len() == 1 {
+            return self.sample_level(base, u, v);
+        }
+
+        let texels = width * ((base.width * base.height) as f32).sqrt();
+        let level = texels.log2().clamp(0.0, (self.levels.len() - 1) as f32);
+        let lower = level.floor() as usize;
+        let t = level - lower as f32;
+
+        let near = self.sample_level(&self.levels[lower], u, v);
+        if t <= 0.0 {
+            return near;
+        }
+        let far = self.sample_level(&self.levels[lower + 1], u, v);
+        std::array::from_fn(|channel| near[channel] + t * (far[channel] - near[channel]))
+    }
+
+    fn sample_level(&self, level: &MipLevel, u: f32, v: f32) -> [f32; 4] {
+        // Rows run down the image while v runs up, and texel centers sit halfway between
+        // whole coordinates
+        let x = u * level.width as f32 - 0.5;
+        let y = (1. - v) * level.height as f32 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        match self.settings.filter {
+            TextureFilter::Nearest => self.texel(level, x.round() as i64, y.round() as i64),
+            TextureFilter::Bilinear => self.weighted(
+                level,
+                [(x0, 1. - tx), (x0 + 1, tx)],
+                [(y0, 1. - ty), (y0 + 1, ty)],
+            ),
+            TextureFilter::Bicubic => {
+                let taps = |start: i64, t: f32| {
+                    let weights = catmull_rom(t);
+                    std::array::from_fn::<_, 4, _>(|i| (start - 1 + i as i64, weights[i]))
+                };
+                // The negative lobes can overshoot around sharp edges
+                let value = self.weighted(level, taps(x0, tx), taps(y0, ty));
+                [
+                    value[0].max(0.),
+                    value[1].max(0.),
+                    value[2].max(0.),
+                    value[3].clamp(0., 1.),
+                ]
+            }
+        }
+    }
+
+    /// Sums texels over the grid of `columns` and `rows`, each given with its weight.
+    fn weighted<const N: usize>(
+        &self,
+        level: &MipLevel,
+        columns: [(i64, f32); N],
+        rows: [(i64, f32); N],
+    ) -> [f32; 4] {
+        let mut sum = [0.; 4];
+        for (y, row_weight) in rows {
+            for (x, column_weight) in columns {
+                let texel = self.texel(level, x, y);
+                for channel in 0..4 {
+                    sum[channel] += row_weight * column_weight * texel[channel];
+                }
+            }
+        }
+        sum
+    }
+
+    fn texel(&self, level: &MipLevel, x: i64, y: i64) -> [f32; 4] {
+        let x = self.wrap(x, level.width);
+        let y = self.wrap(y, level.height);
+        level.texels[y * level.width + x]
+    }
+
+    /// Maps a texel index that may lie outside of the image onto one inside of it.
+    fn wrap(&self, index: i64, size: usize) -> usize {
+        let size = size as i64;
+        let index = match self.settings.wrap {
+            WrapMode::Repeat => index.rem_euclid(size),
+            WrapMode::Mirror => {
+                let index = index.rem_euclid(2 * size);
+                if index < size {
+                    index
+                } else {
+                    2 * size - 1 - index
+                }
+            }
+            WrapMode::Clamp => index.clamp(0, size - 1),
+        };
+        index as usize
     }
 }
 
 impl Texture for ImageTexture {
-    fn value(&self, u: f32, v: f32, _point: &crate::vec3::Vec3) -> crate::color::Color {
-        let u = u.clamp(0., 1.);
-        let v = v.clamp(0., 1.);
+    fn value(&self, u: f32, v: f32, point: &Vec3) -> Color {
+        self.filtered(u, v, point, 0.)
+    }
 
-        let i = ((u * self.image.width() as f32) as u32).min(self.image.width() - 1);
-        let j =
-            (((1. - v) * self.image.height() as f32 - 0.001) as u32).min(self.image.height() - 1);
+    fn filtered(&self, u: f32, v: f32, _point: &Vec3, width: f32) -> Color {
+        let [r, g, b, _] = self.sample(u, v, width);
+        Color::new(r, g, b)
+    }
 
-        let pixel = self.image.get_pixel(i, j);
-        Color::new(
-            pixel[0] as f32 / 255.,
-            pixel[1] as f32 / 255.,
-            pixel[2] as f32 / 255.,
-        )
+    /// Alpha is looked up at full resolution, since averaging it would erode cutouts in
+    /// the distance.
+    fn alpha(&self, u: f32, v: f32, _point: &Vec3) -> f32 {
+        self.sample_level(&self.levels[0], u, v)[3]
     }
 }
+
+/// Images loaded by path, so that a texture used by many objects is only loaded once.
+#[derive(Default)]
+pub struct TextureCache {
+    images: HashMap<(PathBuf, ColorSpace), Arc<Vec<MipLevel>>>,
+}
+
+impl TextureCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(&mut self, filename: &str) -> Result<ImageTexture, TextureError> {
+        self.load_with_settings(filename, TextureSettings::default())
+    }
+
+    /// Loads a texture, reusing the image if it was loaded before. Images are decoded with
+    /// the color space of the settings, so the same file loaded as color and as data is
+    /// kept twice.
+    pub fn load_with_settings(
+        &mut self,
+        filename: &str,
+        settings: TextureSettings,
+    ) -> Result<ImageTexture, TextureError> {
+        let key = (PathBuf::from(filename), settings.color_space);
+        let levels = match self.images.entry(key) {
+            Entry::Occupied(entry) => entry.get().clone(),
+            Entry::Vacant(entry) => {
+                let texture = ImageTexture::from_settings(filename, settings)?;
+                entry.insert(texture.levels).clone()
+            }
+        };
+
+        Ok(ImageTexture { levels, settings })
+    }
+}
+
+/// Opens an image file, keeping Radiance `.hdr` files in floating point, which the generic
+/// decoder converts to 8 bits.
+pub(crate) fn open_image(path: &str) -> ImageResult<DynamicImage> {
+    let is_hdr = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("hdr"));
+    if !is_hdr {
+        return image::open(path);
+    }
+
+    let decoder = HdrDecoder::new(BufReader::new(File::open(path)?))?;
+    let metadata = decoder.metadata();
+    let pixels = decoder.read_image_hdr()?;
+    let image = Rgb32FImage::from_fn(metadata.width, metadata.height, |x, y| {
+        pixels[(y * metadata.width + x) as usize]
+    });
+    Ok(DynamicImage::ImageRgb32F(image))
+}
+
+/// Inverse of the sRGB transfer function, for a value in `0..=1`.
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Builds the mip pyramid by repeatedly averaging blocks of two by two texels.
+fn mip_pyramid(base: MipLevel) -> Vec<MipLevel> {
+    let mut levels = vec![base];
+    loop {
+        let last = levels.last().unwrap();
+        if last.width == 1 && last.height == 1 {
+            return levels;
+        }
+
+        let width = (last.width / 2).max(1);
+        let height = (last.height / 2).max(1);
+        let texels = (0..height)
+            .flat_map(|j| (0..width).map(move |i| (i, j)))
+            .map(|(i, j)| {
+                let xs = [2 * i, (2 * i + 1).min(last.width - 1)];
+                let ys = [2 * j, (2 * j + 1).min(last.height - 1)];
+                let mut sum = [0.; 4];
+                for y in ys {
+                    for x in xs {
+                        let texel = last.texels[y * last.width + x];
+                        for channel in 0..4 {
+                            sum[channel] += 0.25 * texel[channel];
+                        }
+                    }
+                }
+                sum
+            })
+            .collect();
+
+        levels.push(MipLevel {
+            width,
+            height,
+            texels,
+        });
+    }
+}
+
+/// Weights of the four texels around a point `t` of the way between the middle two, for
+/// a Catmull-Rom spline through them.
+fn catmull_rom(t: f32) -> [f32; 4] {
+    [
+        ((-0.5 * t + 1.) * t - 0.5) * t,
+        (1.5 * t - 2.5) * t * t + 1.,
+        ((-1.5 * t + 2.) * t + 0.5) * t,
+        (0.5 * t - 0.5) * t * t,
+    ]
+}
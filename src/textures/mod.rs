@@ -2,19 +2,44 @@ mod checker;
 mod image_texture;
 mod noise;
 mod solid_color;
+mod tinted;
 
 pub use checker::Checker;
-pub use image_texture::ImageTexture;
+pub(crate) use image_texture::open_image;
+pub use image_texture::{ImageTexture, TextureCache, TextureError};
 pub use noise::NoiseTexture;
 pub use solid_color::SolidColor;
+pub use tinted::Tinted;
 
 use dyn_clonable::clonable;
 
-use crate::{color::Color, vec3::Vec3};
+use crate::{color::Color, objects::HitRecord, vec3::Vec3};
 
 #[clonable]
 pub trait Texture: Clone {
     fn value(&self, u: f32, v: f32, point: &Vec3) -> Color;
+
+    /// Value averaged over a square of `width` in texture coordinates around `(u, v)`,
+    /// which keeps detailed textures from aliasing in the distance. Textures that don't
+    /// alias return the value at the point.
+    fn filtered(&self, u: f32, v: f32, point: &Vec3, _width: f32) -> Color {
+        self.value(u, v, point)
+    }
+
+    /// Looks up the filtered value where a ray hit a surface.
+    fn at_hit(&self, hit_record: &HitRecord) -> Color {
+        self.filtered(
+            hit_record.u,
+            hit_record.v,
+            &hit_record.point,
+            hit_record.footprint,
+        )
+    }
+
+    /// Opacity in `0..=1`, textures without an alpha channel are fully opaque.
+    fn alpha(&self, _u: f32, _v: f32, _point: &Vec3) -> f32 {
+        1.0
+    }
 }
 
 pub type BoxedTexture<'a> = Box<dyn Texture + Send + Sync + 'a>;
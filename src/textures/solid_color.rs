@@ -18,3 +18,17 @@ impl Texture for SolidColor {
         self.color
     }
 }
+
+/// Lets a constant color be passed wherever a texture is expected.
+impl Texture for Color {
+    fn value(&self, _u: f32, _v: f32, _point: &Vec3) -> Color {
+        *self
+    }
+}
+
+/// Lets a constant scalar, such as a roughness, be passed wherever a texture is expected.
+impl Texture for f32 {
+    fn value(&self, _u: f32, _v: f32, _point: &Vec3) -> Color {
+        Color::new(*self, *self, *self)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{color::Color, vec3::Vec3};
+
+use super::{BoxedTexture, Texture};
+
+/// Multiplies a texture by a color, which turns grayscale masks into colored patterns such
+/// as neon signs.
+#[derive(Clone)]
+pub struct Tinted<'a> {
+    texture: BoxedTexture<'a>,
+    color: Color,
+}
+
+impl<'a> Tinted<'a> {
+    pub fn new(texture: impl Texture + Send + Sync + 'a, color: Color) -> Self {
+        Self {
+            texture: Box::new(texture),
+            color,
+        }
+    }
+}
+
+impl Texture for Tinted<'_> {
+    fn value(&self, u: f32, v: f32, point: &Vec3) -> Color {
+        self.texture.value(u, v, point) * self.color
+    }
+
+    fn filtered(&self, u: f32, v: f32, point: &Vec3, width: f32) -> Color {
+        self.texture.filtered(u, v, point, width) * self.color
+    }
+
+    fn alpha(&self, u: f32, v: f32, point: &Vec3) -> f32 {
+        self.texture.alpha(u, v, point)
+    }
+}
@@ -114,17 +114,16 @@ impl Vec3 {
     }
 
     pub fn random_in_unit_sphere() -> Vec3 {
-        Vec3::random_on_unit_sphere() * rand::random::<f32>().sqrt()
+        Vec3::random_on_unit_sphere() * rand::random::<f32>().cbrt()
     }
 
     pub fn random_on_unit_sphere() -> Vec3 {
-        let (theta, phi): (f32, f32) = rand::random();
+        let (r1, r2): (f32, f32) = rand::random();
+        let cos_theta = 1.0 - 2.0 * r1;
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * std::f32::consts::PI * r2;
 
-        Vec3::new(
-            theta.sin() * phi.cos(),
-            theta.sin() * phi.sin(),
-            theta.cos(),
-        )
+        Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta)
     }
 
     #[allow(dead_code)]